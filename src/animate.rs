@@ -0,0 +1,282 @@
+use crate::context::Context;
+use crate::error::Result;
+use crate::grid::Point;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    /// Colours to tell apart an unbounded number of things, such as regions.
+    pub const CYCLE: [Colour; 6] = [
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+    ];
+
+    fn ansi(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// What is drawn at one position of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub colour: Option<Colour>,
+}
+
+impl Cell {
+    pub fn plain(ch: char) -> Cell {
+        Cell { ch, colour: None }
+    }
+
+    pub fn coloured(ch: char, colour: Colour) -> Cell {
+        Cell {
+            ch,
+            colour: Some(colour),
+        }
+    }
+}
+
+/// A grid shaped simulation that is advanced and drawn one tick at a time.
+pub trait Simulation {
+    /// Advances by one tick, returning false once there is nothing left to
+    /// do.
+    fn step(&mut self) -> bool;
+
+    fn cell(&self, point: Point) -> Cell;
+
+    /// Smallest and largest corner of the interesting part of the grid.
+    fn bounds(&self) -> (Point, Point);
+
+    /// Position the viewport follows, if any.
+    fn actor(&self) -> Option<Point> {
+        None
+    }
+
+    /// Counters shown on the status line, after the tick.
+    fn status(&self) -> Vec<(&'static str, String)>;
+}
+
+/// The part of the grid that is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(width: usize, height: usize) -> Viewport {
+        Viewport {
+            origin: Point::default(),
+            width,
+            height,
+        }
+    }
+
+    /// Moves to the top left of `bounds`, or centres on the actor of `sim`
+    /// once it comes within a quarter of the size from an edge.
+    pub fn follow(&mut self, sim: &dyn Simulation) {
+        let actor = match sim.actor() {
+            Some(actor) => actor,
+            None => {
+                self.origin = sim.bounds().0;
+                return;
+            }
+        };
+        let (width, height) = (self.width as isize, self.height as isize);
+        let relative = actor - self.origin;
+        if !(width / 4..width - width / 4).contains(&relative.x) {
+            self.origin.x = actor.x - width / 2;
+        }
+        if !(height / 4..height - height / 4).contains(&relative.y) {
+            self.origin.y = actor.y - height / 2;
+        }
+    }
+}
+
+/// One frame: the viewport's cells and a status line with the tick.
+pub fn frame(sim: &dyn Simulation, viewport: &Viewport, tick: usize) -> String {
+    let mut result = String::new();
+    for y in 0..viewport.height as isize {
+        for x in 0..viewport.width as isize {
+            let cell = sim.cell(viewport.origin + Point::new(x, y));
+            match cell.colour {
+                Some(colour) => result += &format!("\x1b[{}m{}\x1b[0m", colour.ansi(), cell.ch),
+                None => result.push(cell.ch),
+            }
+        }
+        result.push('\n');
+    }
+    result += &format!("tick {}", tick);
+    for (name, value) in sim.status() {
+        result += &format!("  {}: {}", name, value);
+    }
+    result.push('\n');
+    result
+}
+
+/// How `animate` moves from one frame to the next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pace {
+    /// Draws `ticks` frames per second.
+    Speed(f64),
+    /// Waits for a line on the input, `q` quits.
+    Step,
+}
+
+/// Runs `sim` to its end or until `ctx` is cancelled or times out,
+/// drawing every frame on `out`. Returns the number of ticks run.
+pub fn animate(
+    sim: &mut dyn Simulation,
+    viewport: &mut Viewport,
+    pace: Pace,
+    ctx: &Context,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    // Hide the cursor and clear the screen.
+    write!(out, "\x1b[?25l\x1b[2J")?;
+    let mut tick = 0;
+    let result = loop {
+        viewport.follow(sim);
+        write!(out, "\x1b[H{}", frame(sim, viewport, tick))?;
+        out.flush()?;
+        if ctx.check().is_err() {
+            break Ok(tick);
+        }
+        match pace {
+            Pace::Speed(speed) => thread::sleep(Duration::from_secs_f64(1.0 / speed)),
+            Pace::Step => {
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 || line.trim() == "q" {
+                    break Ok(tick);
+                }
+            }
+        }
+        if !sim.step() {
+            break Ok(tick);
+        }
+        tick += 1;
+    };
+    write!(out, "\x1b[?25h")?;
+    result
+}
+
+/// Steps `sim` to its end without drawing it, polling `ctx`. Returns the
+/// number of ticks run.
+pub fn finish(sim: &mut dyn Simulation, ctx: &Context) -> Result<usize> {
+    let mut tick = 0;
+    loop {
+        ctx.poll(tick, None)?;
+        if !sim.step() {
+            return Ok(tick as usize);
+        }
+        tick += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A dot walking right on an empty line.
+    struct Walk(isize);
+
+    impl Simulation for Walk {
+        fn step(&mut self) -> bool {
+            self.0 += 1;
+            self.0 < 10
+        }
+
+        fn cell(&self, point: Point) -> Cell {
+            if point == Point::new(self.0, 0) {
+                Cell::coloured('@', Colour::Red)
+            } else {
+                Cell::plain('.')
+            }
+        }
+
+        fn bounds(&self) -> (Point, Point) {
+            (Point::default(), Point::new(10, 0))
+        }
+
+        fn actor(&self) -> Option<Point> {
+            Some(Point::new(self.0, 0))
+        }
+
+        fn status(&self) -> Vec<(&'static str, String)> {
+            vec![("x", self.0.to_string())]
+        }
+    }
+
+    #[test]
+    fn test_frame() {
+        let viewport = Viewport::new(3, 1);
+        assert_eq!(
+            ".\x1b[31m@\x1b[0m.\ntick 4  x: 1\n",
+            frame(&Walk(1), &viewport, 4)
+        );
+    }
+
+    #[test]
+    fn test_follow() {
+        let mut viewport = Viewport::new(8, 1);
+        viewport.follow(&Walk(5));
+        assert_eq!(0, viewport.origin.x);
+        viewport.follow(&Walk(6));
+        assert_eq!(2, viewport.origin.x);
+    }
+
+    #[test]
+    fn test_animate() {
+        let mut out = vec![];
+        let mut input = &b"\n\nq\n"[..];
+        let mut viewport = Viewport::new(4, 1);
+        let ticks = animate(
+            &mut Walk(0),
+            &mut viewport,
+            Pace::Step,
+            &Context::new(),
+            &mut input,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(2, ticks);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("tick 2  x: 2\n\x1b[?25h"));
+
+        let ticks = animate(
+            &mut Walk(0),
+            &mut viewport,
+            Pace::Speed(1000.0),
+            &Context::new(),
+            &mut &b""[..],
+            &mut vec![],
+        )
+        .unwrap();
+        assert_eq!(9, ticks);
+        assert_eq!(9, finish(&mut Walk(0), &Context::new()).unwrap());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::puzzle::Solver;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::time::{Duration, Instant};
+
+/// Wall time of repeated runs of one part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Statistics of `samples`, which must not be empty.
+    pub fn new(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:.1?}  median {:.1?}  max {:.1?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+/// Parses `source` once and solves each of `parts` `iterations` times.
+/// Only the solving is timed.
+pub fn bench(
+    day: &dyn Solver,
+    source: &Source,
+    parts: &[u8],
+    iterations: usize,
+    ctx: &Context,
+) -> Result<Vec<(u8, Stats)>> {
+    let input = source.parse(day)?;
+    let mut result = vec![];
+    for part in parts {
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            day.solve(&input, *part, ctx)?;
+            samples.push(start.elapsed());
+        }
+        result.push((*part, Stats::new(&mut samples)));
+    }
+    Ok(result)
+}
+
+/// Median time of one part from an earlier run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub day: u8,
+    pub part: u8,
+    pub median: Duration,
+}
+
+/// Parses a baseline file: one `day part median_us` per line. Blank lines
+/// and lines starting with `#` are ignored.
+pub fn parse_baseline<R: BufRead>(reader: R) -> Result<Vec<Baseline>> {
+    let mut result = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 3 {
+            let token = fields.get(3).copied().unwrap_or(&line[line.len()..]);
+            return Err(Error::token(
+                i + 1,
+                &line,
+                token,
+                "expected `day part median_us`",
+            ));
+        }
+        let number = |token: &str| {
+            token
+                .parse::<u64>()
+                .map_err(|_| Error::token(i + 1, &line, token, "expected a number"))
+        };
+        let (day, part, median) = (number(fields[0])?, number(fields[1])?, number(fields[2])?);
+        if day > 25 || !(1..=2).contains(&part) {
+            return Err(Error::token(i + 1, &line, fields[0], "invalid day or part"));
+        }
+        result.push(Baseline {
+            day: day as u8,
+            part: part as u8,
+            median: Duration::from_micros(median),
+        });
+    }
+    Ok(result)
+}
+
+/// Writes `baseline` in the format read by `parse_baseline`.
+pub fn write_baseline<W: Write>(mut writer: W, baseline: &[Baseline]) -> Result<()> {
+    writeln!(writer, "# day part median_us")?;
+    for b in baseline {
+        writeln!(writer, "{} {} {}", b.day, b.part, b.median.as_micros())?;
+    }
+    Ok(())
+}
+
+/// Slowdowns smaller than this are timer noise and never a regression,
+/// whatever their relative size.
+pub const NOISE: Duration = Duration::from_millis(1);
+
+/// A median compared with its baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// Relative change in percent, positive if slower.
+    pub change: f64,
+    /// Slower by more than `threshold` percent and by more than `NOISE`.
+    pub regression: bool,
+}
+
+/// Compares `median` with the baseline of the part, if there is one.
+pub fn compare(
+    baseline: &[Baseline],
+    day: u8,
+    part: u8,
+    median: Duration,
+    threshold: f64,
+) -> Option<Comparison> {
+    let before = baseline
+        .iter()
+        .find(|b| b.day == day && b.part == part)?
+        .median;
+    let change =
+        (median.as_secs_f64() - before.as_secs_f64()) / before.as_secs_f64().max(1e-9) * 100.0;
+    Some(Comparison {
+        change,
+        regression: change > threshold && median > before + NOISE,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(&mut [ms(5), ms(1), ms(9), ms(3), ms(4)]);
+        assert_eq!(
+            Stats {
+                min: ms(1),
+                median: ms(4),
+                max: ms(9)
+            },
+            stats
+        );
+        assert_eq!("min 1.0ms  median 4.0ms  max 9.0ms", stats.to_string());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let baseline = vec![
+            Baseline {
+                day: 13,
+                part: 2,
+                median: Duration::from_micros(200_000),
+            },
+            Baseline {
+                day: 15,
+                part: 1,
+                median: Duration::from_micros(1500),
+            },
+        ];
+        let mut file = vec![];
+        write_baseline(&mut file, &baseline).unwrap();
+        assert_eq!(
+            "# day part median_us\n13 2 200000\n15 1 1500\n",
+            String::from_utf8(file.clone()).unwrap()
+        );
+        assert_eq!(baseline, parse_baseline(file.as_slice()).unwrap());
+
+        let ms = Duration::from_millis;
+        let comparison = compare(&baseline, 13, 2, ms(250), 10.0).unwrap();
+        assert!((comparison.change - 25.0).abs() < 1e-9);
+        assert!(comparison.regression);
+        assert!(!compare(&baseline, 13, 2, ms(250), 30.0).unwrap().regression);
+        assert!(!compare(&baseline, 15, 1, ms(2), 10.0).unwrap().regression);
+        assert_eq!(None, compare(&baseline, 13, 1, ms(1), 10.0));
+
+        let e = parse_baseline("13 3 100".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:1:1: invalid day or part (found `13`)",
+            e.to_string()
+        );
+    }
+}
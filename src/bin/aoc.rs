@@ -0,0 +1,508 @@
+use aoc_2017::animate::{self, Viewport};
+use aoc_2017::bench::{self, Baseline};
+use aoc_2017::check::{self, Outcome};
+use aoc_2017::cli::{self, Options};
+use aoc_2017::config::{self, CONFIG_PATH};
+use aoc_2017::context::Context;
+use aoc_2017::examples::{self, Run};
+use aoc_2017::explain::{Explain, Value};
+use aoc_2017::fetch::{self, Fetched};
+use aoc_2017::fuzz;
+use aoc_2017::image;
+use aoc_2017::input::{self, Source};
+use aoc_2017::parallel::{self, Status};
+use aoc_2017::repl::{Reply, Session};
+use aoc_2017::report::Format;
+use aoc_2017::rng::Rng;
+use aoc_2017::runner;
+use aoc_2017::serve;
+use aoc_2017::Solver;
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
+use std::env;
+use std::fs::File;
+use std::io;
+use std::net::TcpListener;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+/// Reads the configuration, then applies the `--param`s to it.
+fn load_config(options: &mut Options) -> aoc_2017::Result<()> {
+    let path = match &options.config_path {
+        Some(path) => Some(path.as_str()),
+        None if Path::new(CONFIG_PATH).exists() => Some(CONFIG_PATH),
+        None => None,
+    };
+    if let Some(path) = path {
+        options.config = config::parse_config(input::open(path)?).map_err(|e| e.with_path(path))?;
+    }
+    for (name, value) in &options.params {
+        options.config.set(options.days[0], name, value)?;
+    }
+    Ok(())
+}
+
+fn source(day: &dyn Solver, options: &Options) -> Source {
+    match &options.input {
+        Some(source) => source.clone(),
+        None => Source::default_for(day),
+    }
+}
+
+fn selected_days(options: &Options) -> impl Iterator<Item = &'static dyn Solver> + '_ {
+    aoc_2017::days()
+        .iter()
+        .copied()
+        .filter(move |d| options.days.contains(&d.day()))
+}
+
+/// Context for solving `day` with its parameters, stopped by Ctrl-C or
+/// `--timeout`.
+fn context(day: &dyn Solver, options: &Options) -> Context {
+    let mut ctx = Context::new()
+        .with_canceller(options.canceller.clone())
+        .with_params(options.config.params(day.day()));
+    if let Some(timeout) = options.timeout {
+        ctx = ctx.with_timeout(timeout);
+    }
+    if options.progress {
+        let day = day.day();
+        ctx = ctx.with_progress(Duration::from_secs(1), move |progress| {
+            eprintln!("day {}: {}", day, progress)
+        });
+    }
+    ctx
+}
+
+fn run(options: &Options) -> bool {
+    let mut success = true;
+    if let Some(header) = options.format.header() {
+        println!("{}", header);
+    }
+    for day in selected_days(options) {
+        let explain = Explain::new();
+        let mut ctx = context(day, options);
+        if options.explain {
+            ctx = ctx.with_explain(explain.clone());
+        }
+        runner::run_each(day, &source(day, options), &options.parts, &ctx, |answer| {
+            let line = options.format.line(&answer);
+            match (&answer.answer, options.format) {
+                (Err(_), Format::Text) => eprintln!("{}", line),
+                _ => println!("{}", line),
+            }
+            for event in explain.take() {
+                match options.format {
+                    Format::Json => {
+                        let prefix = [
+                            ("day", Value::from(answer.day)),
+                            ("part", Value::from(answer.part)),
+                        ];
+                        println!("{}", event.json(&prefix))
+                    }
+                    _ => println!("    {}", event.text()),
+                }
+            }
+            success &= answer.answer.is_ok();
+        });
+    }
+    success
+}
+
+fn run_parallel(options: &Options) -> bool {
+    let jobs = selected_days(options)
+        .map(|day| (day, source(day, options)))
+        .collect();
+    let rows = parallel::run_all(
+        jobs,
+        &options.parts,
+        options.jobs,
+        options.timeout.unwrap_or(Duration::from_secs(60)),
+        &options.canceller,
+        &options.config,
+    );
+    print!("{}", parallel::table(&rows));
+    rows.iter().all(|row| row.status == Status::Ok)
+}
+
+fn run_check(options: &Options) -> aoc_2017::Result<bool> {
+    let expected = check::parse_answers(input::open(&options.answers)?)
+        .map_err(|e| e.with_path(&options.answers))?;
+    let (mut passed, mut mismatched, mut failed, mut unknown) = (0, 0, 0, 0);
+    for day in selected_days(options) {
+        let ctx = context(day, options);
+        for answer in runner::run(day, &source(day, options), &options.parts, &ctx) {
+            let outcome = check::check(&answer, &expected);
+            match outcome {
+                Outcome::Pass => passed += 1,
+                Outcome::Mismatch { .. } => mismatched += 1,
+                Outcome::Fail(_) => failed += 1,
+                Outcome::Unknown => unknown += 1,
+            }
+            println!("day {} part {}: {}", answer.day, answer.part, outcome);
+        }
+    }
+    println!(
+        "{} passed, {} mismatched, {} failed, {} unknown",
+        passed, mismatched, failed, unknown
+    );
+    Ok(mismatched == 0 && failed == 0)
+}
+
+fn run_examples(options: &Options) -> aoc_2017::Result<bool> {
+    let mut success = true;
+    for example in examples::discover(Path::new(examples::EXAMPLES_DIR))? {
+        if !options.days.contains(&example.day) && aoc_2017::day(example.day).is_some() {
+            continue;
+        }
+        let path = example.path.display();
+        let run = example.run();
+        success &= run.is_ok();
+        match run {
+            Run::Parts(parts) => {
+                for (part, outcome) in parts {
+                    println!("{} part {}: {}", path, part, outcome);
+                }
+            }
+            Run::NotImplemented => {
+                println!("{}: skipped, day {} is not implemented", path, example.day)
+            }
+            Run::BadSidecar(e) => println!("{}: FAIL: {}", path, e),
+        }
+    }
+    Ok(success)
+}
+
+fn run_fuzz(options: &Options) -> bool {
+    // The failures are reported below, with their input.
+    panic::set_hook(Box::new(|_| {}));
+    let mut success = true;
+    for day in selected_days(options) {
+        let mut rng = Rng::new(options.seed ^ u64::from(day.day()));
+        match fuzz::fuzz(day, &mut rng, options.cases) {
+            Some(failure) => {
+                println!("{}", failure);
+                success = false;
+            }
+            None => println!("day {}: {} cases ok", day.day(), options.cases),
+        }
+    }
+    success
+}
+
+fn run_animate(options: &Options) -> aoc_2017::Result<bool> {
+    let day = aoc_2017::day(options.days[0]).unwrap();
+    let part = options.parts[0];
+    let input = source(day, options).parse(day)?;
+    let ctx = context(day, options);
+    let mut sim = day.animation(&input, part, &ctx)?.ok_or_else(|| {
+        aoc_2017::Error::Solve(format!(
+            "day {} part {} cannot be animated",
+            day.day(),
+            part
+        ))
+    })?;
+    if let Some(path) = &options.render {
+        animate::finish(sim.as_mut(), &ctx)?;
+        let format = image::Format::from_path(Path::new(path)).unwrap();
+        let snapshot = image::snapshot(sim.as_ref());
+        let mut file = File::create(path).map_err(|e| aoc_2017::Error::from(e).with_path(path))?;
+        image::write(&snapshot, format, 10, &mut file)?;
+        return Ok(true);
+    }
+    let mut viewport = Viewport::new(options.view.0, options.view.1);
+    animate::animate(
+        sim.as_mut(),
+        &mut viewport,
+        options.pace,
+        &ctx,
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+    )?;
+    Ok(true)
+}
+
+fn run_serve(options: &Options) -> aoc_2017::Result<bool> {
+    let listener = TcpListener::bind(("127.0.0.1", options.port))?;
+    println!("listening on http://{}", listener.local_addr()?);
+    let timeout = options.timeout.unwrap_or(Duration::from_secs(60));
+    serve::serve(listener, options.jobs, Some(timeout), &options.canceller)?;
+    Ok(true)
+}
+
+fn run_fetch(options: &Options) -> bool {
+    let mut success = true;
+    for day in selected_days(options) {
+        let path = match Source::default_for(day) {
+            Source::Path(path) => path,
+            _ => unreachable!("default inputs are files"),
+        };
+        let session = options.session.as_deref();
+        match fetch::fetch(day.day(), &options.base_url, session, Path::new(&path)) {
+            Ok(Fetched::Cached) => println!("day {}: {} is cached", day.day(), path),
+            Ok(Fetched::Downloaded(len)) => {
+                println!("day {}: saved {} bytes to {}", day.day(), len, path)
+            }
+            Err(e) => {
+                eprintln!("day {}: error: {}", day.day(), e);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+fn run_lint(options: &Options) -> bool {
+    let mut success = true;
+    for day in selected_days(options) {
+        let source = source(day, options);
+        let problems = match source.read() {
+            Ok(data) => day.lint(&mut data.as_slice(), &context(day, options)),
+            Err(e) => vec![e],
+        };
+        if problems.is_empty() {
+            println!("day {}: {} is ok", day.day(), source.name());
+        }
+        for problem in problems {
+            println!("{}", problem.with_path(source.name()));
+            success = false;
+        }
+    }
+    success
+}
+
+struct ReplHelper {
+    session: Session,
+    files: FilenameCompleter,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        match self.session.complete(&line[..pos]) {
+            Some((start, words)) => {
+                let pairs = words
+                    .into_iter()
+                    .map(|word| Pair {
+                        display: word.clone(),
+                        replacement: word,
+                    })
+                    .collect();
+                Ok((start, pairs))
+            }
+            None => self.files.complete(line, pos, ctx),
+        }
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".aoc_history"))
+}
+
+fn run_repl(options: &Options) -> aoc_2017::Result<bool> {
+    let mut session = Session::with_config(options.config.clone());
+    if options.days.len() == 1 {
+        let day = aoc_2017::day(options.days[0]).unwrap();
+        session.load(day, source(day, options))?;
+    }
+    let mut editor: Editor<ReplHelper, DefaultHistory> =
+        Editor::new().map_err(|e| aoc_2017::Error::Solve(e.to_string()))?;
+    editor.set_helper(Some(ReplHelper {
+        session,
+        files: FilenameCompleter::new(),
+    }));
+    let history = history_path();
+    if let Some(path) = &history {
+        // There is no history on the first run.
+        let _ = editor.load_history(path);
+    }
+    loop {
+        let helper = editor.helper().unwrap();
+        let prompt = helper.session.prompt();
+        let day = helper.session.day();
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(aoc_2017::Error::Solve(e.to_string())),
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+        let ctx = || match day {
+            Some(day) => context(day, options),
+            None => Context::new().with_canceller(options.canceller.clone()),
+        };
+        let reply = editor.helper_mut().unwrap().session.execute(&line, &ctx);
+        options.canceller.reset();
+        match reply {
+            Ok(Reply::Print(text)) if text.is_empty() => {}
+            Ok(Reply::Print(text)) => println!("{}", text),
+            Ok(Reply::Quit) => break,
+            Err(e) => eprintln!("error: {}", e),
+        }
+    }
+    if let Some(path) = &history {
+        if let Err(e) = editor.save_history(path) {
+            eprintln!("warning: cannot save history to {}: {}", path.display(), e);
+        }
+    }
+    Ok(true)
+}
+
+fn run_bench(options: &Options) -> aoc_2017::Result<bool> {
+    let baseline = match &options.baseline {
+        Some(path) => bench::parse_baseline(input::open(path)?).map_err(|e| e.with_path(path))?,
+        None => vec![],
+    };
+    let mut success = true;
+    let mut medians = vec![];
+    for day in selected_days(options) {
+        let stats = match bench::bench(
+            day,
+            &source(day, options),
+            &options.parts,
+            options.iterations,
+            &context(day, options),
+        ) {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("day {}: error: {}", day.day(), e);
+                success = false;
+                continue;
+            }
+        };
+        for (part, stats) in stats {
+            let mut line = format!("day {:2} part {}: {}", day.day(), part, stats);
+            let comparison =
+                bench::compare(&baseline, day.day(), part, stats.median, options.threshold);
+            if let Some(comparison) = comparison {
+                line += &format!("  ({:+.1}%", comparison.change);
+                if comparison.regression {
+                    line += " REGRESSION";
+                    success = false;
+                }
+                line += ")";
+            }
+            println!("{}", line);
+            medians.push(Baseline {
+                day: day.day(),
+                part,
+                median: stats.median,
+            });
+        }
+    }
+    if let Some(path) = &options.save_baseline {
+        let file = File::create(path).map_err(|e| aoc_2017::Error::from(e).with_path(path))?;
+        bench::write_baseline(file, &medians)?;
+    }
+    Ok(success)
+}
+
+fn main() {
+    let mut options = match cli::parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    if let Err(e) = load_config(&mut options) {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    }
+
+    if options.list {
+        for day in aoc_2017::days() {
+            println!("day {:2}  {}", day.day(), Source::default_for(*day).name());
+            let params = options.config.params(day.day());
+            for (name, description) in day.params() {
+                match params.get(*name) {
+                    Some(value) => println!("        {} = {}: {}", name, value, description),
+                    None => println!("        {}: {}", name, description),
+                }
+            }
+        }
+        return;
+    }
+
+    if options.generate {
+        let day = aoc_2017::day(options.days[0]).unwrap();
+        print!(
+            "{}",
+            day.generate(&mut Rng::new(options.seed), options.size)
+        );
+        return;
+    }
+
+    let canceller = options.canceller.clone();
+    let handler = ctrlc::set_handler(move || {
+        if canceller.is_cancelled() {
+            // The solver does not poll its context, give up on it.
+            process::exit(130);
+        }
+        eprintln!("cancelling, press Ctrl-C again to exit immediately");
+        canceller.cancel();
+    });
+    if let Err(e) = handler {
+        eprintln!("warning: cannot handle Ctrl-C: {}", e);
+    }
+
+    let result = if options.serve {
+        run_serve(&options)
+    } else if options.fetch {
+        Ok(run_fetch(&options))
+    } else if options.repl {
+        run_repl(&options)
+    } else if options.lint {
+        Ok(run_lint(&options))
+    } else if options.parallel {
+        Ok(run_parallel(&options))
+    } else if options.check {
+        run_check(&options)
+    } else if options.fuzz {
+        Ok(run_fuzz(&options))
+    } else if options.examples {
+        run_examples(&options)
+    } else if options.bench {
+        run_bench(&options)
+    } else if options.animate || options.render.is_some() {
+        run_animate(&options)
+    } else {
+        Ok(run(&options))
+    };
+    let success = result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        false
+    });
+    if !success {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::error::{Error, Result};
+use crate::runner::Answer;
+use std::fmt;
+use std::io::BufRead;
+
+/// Default location of the recorded answers.
+pub const ANSWERS_PATH: &str = "resources/answers.txt";
+
+/// A known correct answer for one part of one day's input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub answer: String,
+}
+
+/// Formats as a line of the answers file.
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.day, self.part, self.input_hash, self.answer
+        )
+    }
+}
+
+/// Parses an answers file: one `day part input_hash answer` per line. Blank
+/// lines and lines starting with `#` are ignored.
+pub fn parse_answers<R: BufRead>(reader: R) -> Result<Vec<Expected>> {
+    let mut result = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 {
+            let token = fields.get(4).copied().unwrap_or(&line[line.len()..]);
+            return Err(Error::token(
+                i + 1,
+                &line,
+                token,
+                "expected `day part input_hash answer`",
+            ));
+        }
+        let number = |token: &str, message| {
+            token
+                .parse::<u8>()
+                .map_err(|_| Error::token(i + 1, &line, token, message))
+        };
+        let day = number(fields[0], "invalid day")?;
+        let part = number(fields[1], "invalid part")?;
+        if part != 1 && part != 2 {
+            return Err(Error::token(i + 1, &line, fields[1], "invalid part"));
+        }
+        result.push(Expected {
+            day,
+            part,
+            input_hash: fields[2].to_string(),
+            answer: fields[3].to_string(),
+        });
+    }
+    Ok(result)
+}
+
+/// Result of comparing an answer with the recorded ones.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Pass,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The solver returned an error.
+    Fail(Error),
+    /// No answer is recorded for this input.
+    Unknown,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Outcome::Pass | Outcome::Unknown)
+    }
+}
+
+pub fn check(answer: &Answer, expected: &[Expected]) -> Outcome {
+    let recorded = expected.iter().find(|e| {
+        e.day == answer.day && e.part == answer.part && e.input_hash == answer.input_hash
+    });
+    compare(answer, recorded.map(|e| e.answer.as_str()))
+}
+
+/// Compares `answer` with `expected`, `Outcome::Unknown` if there is no
+/// expected answer.
+pub fn compare(answer: &Answer, expected: Option<&str>) -> Outcome {
+    match (&answer.answer, expected) {
+        (Err(e), _) => Outcome::Fail(e.clone()),
+        (Ok(_), None) => Outcome::Unknown,
+        (Ok(actual), Some(expected)) if actual == expected => Outcome::Pass,
+        (Ok(actual), Some(expected)) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        },
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Mismatch { expected, actual } => {
+                write!(f, "MISMATCH: expected {}, got {}", expected, actual)
+            }
+            Outcome::Fail(e) => write!(f, "FAIL: {}", e),
+            Outcome::Unknown => write!(f, "unknown input"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn answer(answer: Result<String>) -> Answer {
+        Answer {
+            day: 1,
+            part: 2,
+            answer,
+            parse_time: Duration::default(),
+            solve_time: Duration::default(),
+            input_hash: "00ff".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers =
+            parse_answers("# day part hash answer\n\n1 2 00ff 1358\n".as_bytes()).unwrap();
+        assert_eq!(
+            vec![Expected {
+                day: 1,
+                part: 2,
+                input_hash: "00ff".to_string(),
+                answer: "1358".to_string()
+            }],
+            answers
+        );
+        assert_eq!("1 2 00ff 1358", answers[0].to_string());
+
+        let e = parse_answers("1 3 00ff 1358".as_bytes()).unwrap_err();
+        assert_eq!("<input>:1:3: invalid part (found `3`)", e.to_string());
+        let e = parse_answers("1 2 00ff".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:1:9: expected `day part input_hash answer` (found nothing)",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = parse_answers("1 2 00ff 1358\n".as_bytes()).unwrap();
+        assert!(matches!(
+            check(&answer(Ok("1358".to_string())), &expected),
+            Outcome::Pass
+        ));
+        assert_eq!(
+            "MISMATCH: expected 1358, got 1357",
+            check(&answer(Ok("1357".to_string())), &expected).to_string()
+        );
+        assert!(matches!(
+            check(&answer(Ok("1358".to_string())), &[]),
+            Outcome::Unknown
+        ));
+        let outcome = check(&answer(Err(Error::solve("no route"))), &expected);
+        assert!(!outcome.is_ok());
+        assert_eq!("FAIL: no route", outcome.to_string());
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let answers = parse_answers(crate::input::open(ANSWERS_PATH).unwrap()).unwrap();
+        for day in crate::days() {
+            for part in 1..=2 {
+                assert!(answers.iter().any(|e| e.day == day.day() && e.part == part));
+            }
+        }
+    }
+}
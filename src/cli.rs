@@ -0,0 +1,575 @@
+use crate::animate::Pace;
+use crate::check;
+use crate::config::Config;
+use crate::context::Canceller;
+use crate::fetch;
+use crate::image;
+use crate::input::Source;
+use crate::report::Format;
+use std::env;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Errors are the messages to print before the usage.
+type Result<T> = std::result::Result<T, String>;
+
+pub const USAGE: &str = "Usage: aoc serve [--port N] [--jobs N] [--timeout SECS]
+       aoc fetch [--day DAYS] [--session TOKEN] [--base-url URL]
+       aoc repl [--day DAY [--input PATH]] [--timeout SECS] [--progress]
+       aoc lint [--day DAYS] [--config PATH] [--param NAME=VALUE]... [FILE]
+       aoc [--list] [--day DAYS] [--part 1|2] [--input PATH | --input-str TEXT]
+           [--config PATH] [--param NAME=VALUE]...
+           [--format text|json|csv] [--explain] [--check [--answers PATH]]
+           [--examples] [--parallel [--jobs N]] [--timeout SECS] [--progress]
+           [--generate [--seed N] [--size N]] [--fuzz [--seed N] [--cases N]]
+           [--bench [--iterations N] [--baseline PATH] [--save-baseline PATH]
+            [--threshold PERCENT]]
+           [--animate [--speed TICKS | --step] [--view WIDTHxHEIGHT]]
+           [--render PATH]
+
+Serve:
+    answers `POST /days/{n}/parts/{p}` with the input as body and
+    `GET /days` in JSON on 127.0.0.1, with --jobs threads; requests that
+    find them all busy and as many already waiting get 503
+    --port N            port to listen on (default: 8017, 0 picks a free one)
+
+Fetch:
+    downloads the inputs of the days to where they are read from, skipping
+    the ones already there
+    --session TOKEN     value of the site's session cookie
+                        (default: $AOC_SESSION)
+    --base-url URL      where to download from (default: $AOC_BASE_URL or
+                        https://adventofcode.com/2017)
+
+Repl:
+    an interactive shell to load inputs, solve them, inspect the
+    intermediate structures and change parameters; `help` lists the
+    commands, the history is kept in ~/.aoc_history
+
+Lint:
+    checks the inputs of the days, or FILE (`-` for stdin) for the single
+    --day, without solving them, and prints every problem with its
+    location: malformed lines and what the solvers cannot handle, such as
+    one-way pipes (day 12), scanner ranges below 2 (day 13), positions out
+    of the line of programs (day 16), `mod` or `jgz` by 0 and `rcv` of a
+    number (day 18) and maps without a middle (day 22)
+
+Options:
+    --list              list the available days and their default inputs
+    --day DAYS          days to run, e.g. `12`, `1,4,9` or `10-15` (default: all);
+                        ranges skip the days that are not implemented
+    --part 1|2          part to run (default: both)
+    --config PATH       parameters of the days (default: aoc.toml, if it
+                        exists), see Configuration below
+    --param NAME=VALUE  set a parameter of the single --day, overriding the
+                        configuration; may be repeated
+    --input PATH        input file, `-` reads stdin
+    --input-str TEXT    the puzzle input itself
+                        (--input and --input-str need a single --day)
+    --format FORMAT     `text` (default), `json` for one JSON object per line
+                        or `csv`; json and csv include timings in
+                        microseconds and the input's FNV-1a hash
+    --explain           follow each answer with the steps it was derived from,
+                        indented or as one JSON object per line with
+                        --format json (days 1, 2, 13 and 19)
+    --parallel          run the days on a pool of threads and print a table
+                        of the answers, times and statuses
+    --jobs N            worker threads for --parallel and serve
+                        (default: number of CPUs)
+    --timeout SECS      give up on a day after SECS seconds (default: none,
+                        60 for --parallel and serve)
+    --progress          report the progress of long loops on stderr
+    --check             compare the answers with the recorded ones and exit
+                        with an error on any mismatch or failure
+    --answers PATH      recorded answers for --check
+                        (default: resources/answers.txt)
+    --examples          run the examples in resources/ (dayN_test*) and
+                        compare with the answers in their .expected files,
+                        which may also set parameters (`name = value`)
+    --generate          print a random input for the single --day
+    --fuzz              feed random and mangled inputs to the parsers (and to
+                        day 9's solver) and report any panic or hang
+    --cases N           inputs per day for --fuzz (default: 10000)
+    --seed N            seed for --generate and --fuzz (default: 0)
+    --size N            rough number of lines or items for --generate
+                        (default: 20)
+    --bench             time each part repeatedly and print min, median and
+                        max wall time of the solver
+    --iterations N      runs per part for --bench (default: 5)
+    --baseline PATH     compare the medians with a saved baseline and exit
+                        with an error if any is slower by more than the
+                        threshold (and by more than 1ms)
+    --save-baseline PATH
+                        save the medians as a baseline
+    --threshold PERCENT allowed slowdown against the baseline (default: 10)
+    --animate           draw the simulation of the single --day and the first
+                        --part in the terminal (days 13, 14, 19 and 22)
+    --speed TICKS       ticks per second for --animate (default: 10)
+    --step              advance --animate on Enter instead, `q` quits
+    --view WxH          size of the --animate viewport (default: 80x20)
+    --render PATH       run the simulation of --animate to its end and save
+                        it as an image, .pbm, .pgm, .ppm or .svg
+
+Configuration:
+    a TOML file with a table per day giving values to its parameters,
+    which --list shows, such as:
+        [day15]
+        a = 65
+        pairs1 = 40_000_000
+
+Environment:
+    AOC_INPUT_DIR       directory holding the default dayN_input files
+                        (default: resources)";
+
+/// The command line, see `USAGE`.
+#[derive(Debug)]
+pub struct Options {
+    /// Print `USAGE` and exit, the other options are not all read.
+    pub help: bool,
+    pub list: bool,
+    pub config_path: Option<String>,
+    pub params: Vec<(String, String)>,
+    /// Read from `config_path`, with `params` applied.
+    pub config: Config,
+    pub days: Vec<u8>,
+    pub parts: Vec<u8>,
+    pub input: Option<Source>,
+    pub format: Format,
+    pub explain: bool,
+    pub parallel: bool,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub progress: bool,
+    /// Cancelled by Ctrl-C.
+    pub canceller: Canceller,
+    pub check: bool,
+    pub answers: String,
+    pub examples: bool,
+    pub generate: bool,
+    pub fuzz: bool,
+    pub cases: usize,
+    pub seed: u64,
+    pub size: usize,
+    pub bench: bool,
+    pub iterations: usize,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub threshold: f64,
+    pub animate: bool,
+    pub pace: Pace,
+    pub view: (usize, usize),
+    pub render: Option<String>,
+    pub serve: bool,
+    pub port: u16,
+    pub fetch: bool,
+    pub repl: bool,
+    pub lint: bool,
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+/// Days such as `12`, `1,4,9` or `10-15`, the ranges skipping the days
+/// that are not implemented.
+pub fn parse_days(s: &str) -> Result<Vec<u8>> {
+    let mut result = vec![];
+    for item in s.split(',') {
+        let bounds = item
+            .split('-')
+            .map(|n| {
+                n.trim()
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid day: {}", item))
+            })
+            .collect::<Result<Vec<u8>>>()?;
+        match bounds.as_slice() {
+            [day] => result.push(*day),
+            [first, last] if first <= last => {
+                result.extend((*first..=*last).filter(|day| crate::day(*day).is_some()))
+            }
+            _ => return Err(format!("invalid day range: {}", item)),
+        }
+    }
+    Ok(result)
+}
+
+/// Parses the arguments after the program name and checks that the
+/// options go together.
+pub fn parse_args<I>(args: I) -> Result<Options>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options {
+        help: false,
+        list: false,
+        config_path: None,
+        params: vec![],
+        config: Config::new(),
+        days: crate::days().iter().map(|d| d.day()).collect(),
+        parts: vec![1, 2],
+        input: None,
+        format: Format::Text,
+        explain: false,
+        parallel: false,
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        timeout: None,
+        progress: false,
+        canceller: Canceller::new(),
+        check: false,
+        answers: check::ANSWERS_PATH.to_string(),
+        examples: false,
+        generate: false,
+        fuzz: false,
+        cases: 10_000,
+        seed: 0,
+        size: 20,
+        bench: false,
+        iterations: 5,
+        baseline: None,
+        save_baseline: None,
+        threshold: 10.0,
+        animate: false,
+        pace: Pace::Speed(10.0),
+        view: (80, 20),
+        render: None,
+        serve: false,
+        port: 8017,
+        fetch: false,
+        repl: false,
+        lint: false,
+        session: env::var(fetch::SESSION_VAR).ok(),
+        base_url: env::var(fetch::BASE_URL_VAR).unwrap_or_else(|_| fetch::BASE_URL.to_string()),
+    };
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("serve") => options.serve = true,
+        Some("fetch") => options.fetch = true,
+        Some("repl") => options.repl = true,
+        Some("lint") => options.lint = true,
+        _ => {}
+    }
+    if options.serve || options.fetch || options.repl || options.lint {
+        args.next();
+    }
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--list" => options.list = true,
+            "--day" => options.days = parse_days(&value()?)?,
+            "--part" => {
+                options.parts = match value()?.as_str() {
+                    "1" => vec![1],
+                    "2" => vec![2],
+                    part => return Err(format!("invalid part: {}", part)),
+                }
+            }
+            "--config" => options.config_path = Some(value()?),
+            "--param" => {
+                let param = value()?;
+                match param.split_once('=') {
+                    Some((name, value)) => options
+                        .params
+                        .push((name.trim().to_string(), value.to_string())),
+                    None => return Err(format!("invalid param: {}", param)),
+                }
+            }
+            "--input" => options.input = Some(Source::from_arg(&value()?)),
+            "--input-str" => options.input = Some(Source::Inline(value()?)),
+            "--format" => {
+                let name = value()?;
+                options.format =
+                    Format::from_name(&name).ok_or_else(|| format!("invalid format: {}", name))?
+            }
+            "--explain" => options.explain = true,
+            "--parallel" => options.parallel = true,
+            "--jobs" => {
+                let jobs = value()?;
+                options.jobs = match jobs.parse() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => return Err(format!("invalid jobs: {}", jobs)),
+                }
+            }
+            "--timeout" => {
+                let secs = value()?;
+                options.timeout = match secs.parse::<f64>() {
+                    Ok(secs) if secs > 0.0 && secs.is_finite() => {
+                        Some(Duration::from_secs_f64(secs))
+                    }
+                    _ => return Err(format!("invalid timeout: {}", secs)),
+                }
+            }
+            "--progress" => options.progress = true,
+            "--check" => options.check = true,
+            "--answers" => options.answers = value()?,
+            "--examples" => options.examples = true,
+            "--generate" => options.generate = true,
+            "--fuzz" => options.fuzz = true,
+            "--cases" => {
+                let cases = value()?;
+                options.cases = cases
+                    .parse()
+                    .map_err(|_| format!("invalid cases: {}", cases))?
+            }
+            "--seed" => {
+                let seed = value()?;
+                options.seed = seed
+                    .parse()
+                    .map_err(|_| format!("invalid seed: {}", seed))?
+            }
+            "--size" => {
+                let size = value()?;
+                options.size = size
+                    .parse()
+                    .map_err(|_| format!("invalid size: {}", size))?
+            }
+            "--bench" => options.bench = true,
+            "--iterations" => {
+                let n = value()?;
+                options.iterations = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid iterations: {}", n)),
+                }
+            }
+            "--baseline" => options.baseline = Some(value()?),
+            "--save-baseline" => options.save_baseline = Some(value()?),
+            "--threshold" => {
+                let t = value()?;
+                options.threshold = match t.parse() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => return Err(format!("invalid threshold: {}", t)),
+                }
+            }
+            "--animate" => options.animate = true,
+            "--speed" => {
+                let speed = value()?;
+                options.pace = match speed.parse::<f64>() {
+                    Ok(speed) if speed > 0.0 && speed.is_finite() => Pace::Speed(speed),
+                    _ => return Err(format!("invalid speed: {}", speed)),
+                }
+            }
+            "--step" => options.pace = Pace::Step,
+            "--view" => {
+                let view = value()?;
+                options.view = match view.split_once('x').map(|(w, h)| (w.parse(), h.parse())) {
+                    Some((Ok(width), Ok(height))) if width > 0 && height > 0 => (width, height),
+                    _ => return Err(format!("invalid view: {}", view)),
+                }
+            }
+            "--port" => {
+                let port = value()?;
+                options.port = port
+                    .parse()
+                    .map_err(|_| format!("invalid port: {}", port))?
+            }
+            "--session" => options.session = Some(value()?),
+            "--base-url" => options.base_url = value()?,
+            "--render" => {
+                let path = value()?;
+                if image::Format::from_path(Path::new(&path)).is_none() {
+                    return Err(format!("unknown image format: {}", path));
+                }
+                options.render = Some(path);
+            }
+            "-h" | "--help" => {
+                options.help = true;
+                return Ok(options);
+            }
+            path if options.lint && !path.starts_with("--") && options.input.is_none() => {
+                options.input = Some(Source::from_arg(path))
+            }
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+    let modes: Vec<&str> = [
+        (options.serve, "serve"),
+        (options.fetch, "fetch"),
+        (options.repl, "repl"),
+        (options.lint, "lint"),
+        (options.list, "--list"),
+        (options.generate, "--generate"),
+        (options.parallel, "--parallel"),
+        (options.check, "--check"),
+        (options.fuzz, "--fuzz"),
+        (options.examples, "--examples"),
+        (options.bench, "--bench"),
+        (options.animate, "--animate"),
+        (options.render.is_some() && !options.animate, "--render"),
+    ]
+    .iter()
+    .filter(|(on, _)| *on)
+    .map(|(_, mode)| *mode)
+    .collect();
+    if let [first, second, ..] = modes.as_slice() {
+        return Err(format!("{} and {} cannot be used together", first, second));
+    }
+    if let Some(day) = options.days.iter().find(|day| crate::day(**day).is_none()) {
+        return Err(format!("day {} is not implemented", day));
+    }
+    if options.generate && options.days.len() != 1 {
+        return Err("--generate requires exactly one --day".to_string());
+    }
+    if (options.animate || options.render.is_some()) && options.days.len() != 1 {
+        return Err("--animate and --render require exactly one --day".to_string());
+    }
+    if options.explain && options.format == Format::Csv {
+        return Err("--explain does not support --format csv".to_string());
+    }
+    if !options.params.is_empty() && options.days.len() != 1 {
+        return Err("--param requires exactly one --day".to_string());
+    }
+    if options.lint && options.input.is_some() && options.days.len() != 1 {
+        return Err("lint FILE requires exactly one --day".to_string());
+    }
+    if options.input.is_some() && options.days.len() != 1 {
+        return Err("--input and --input-str require exactly one --day".to_string());
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(vec![12], parse_days("12").unwrap());
+        assert_eq!(vec![1, 4, 9], parse_days("1,4,9").unwrap());
+        assert_eq!(vec![1, 2, 4, 5], parse_days("1-5").unwrap());
+        assert_eq!(vec![20, 22, 1], parse_days("20 - 23,1").unwrap());
+        assert_eq!("invalid day: x", parse_days("1,x").unwrap_err());
+        assert_eq!("invalid day: 1-", parse_days("1-").unwrap_err());
+        assert_eq!("invalid day range: 5-1", parse_days("5-1").unwrap_err());
+        assert_eq!("invalid day range: 1-2-3", parse_days("1-2-3").unwrap_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse("").unwrap();
+        assert_eq!(vec![1, 2], options.parts);
+        assert_eq!(crate::days().len(), options.days.len());
+
+        let options = parse("--day 15 --part 2").unwrap();
+        assert_eq!((vec![15], vec![2]), (options.days, options.parts));
+        assert_eq!("invalid part: 3", parse("--part 3").unwrap_err());
+        assert_eq!("day 3 is not implemented", parse("--day 3").unwrap_err());
+        assert_eq!("missing value for --day", parse("--day").unwrap_err());
+        assert_eq!("unknown argument: --dya", parse("--dya 1").unwrap_err());
+        assert!(parse("--day 3 --help").unwrap().help);
+    }
+
+    #[test]
+    fn test_input() {
+        let options = parse("--day 1 --input-str 1122").unwrap();
+        assert_eq!(Some(Source::Inline("1122".to_string())), options.input);
+        assert_eq!(
+            "--input and --input-str require exactly one --day",
+            parse("--day 1,2 --input-str 1122").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(7, parse("--generate --day 13 --seed 7").unwrap().seed);
+        assert_eq!(
+            "--generate requires exactly one --day",
+            parse("--day 1-5 --generate").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_parallel() {
+        let options = parse("--parallel --jobs 3 --timeout 1.5").unwrap();
+        assert_eq!(3, options.jobs);
+        assert_eq!(Some(Duration::from_millis(1500)), options.timeout);
+        assert_eq!(None, parse("").unwrap().timeout);
+        assert_eq!("invalid jobs: 0", parse("--jobs 0").unwrap_err());
+        for timeout in ["0", "-1", "inf", "NaN", "soon"] {
+            let e = parse(&format!("--timeout {}", timeout)).unwrap_err();
+            assert_eq!(format!("invalid timeout: {}", timeout), e);
+        }
+    }
+
+    #[test]
+    fn test_animate() {
+        let options = parse("--animate --day 13 --view 40x10 --step").unwrap();
+        assert_eq!((40, 10), options.view);
+        assert_eq!(Pace::Step, options.pace);
+        assert_eq!("invalid view: 80by20", parse("--view 80by20").unwrap_err());
+        assert_eq!("invalid speed: 0", parse("--speed 0").unwrap_err());
+        assert_eq!(
+            "--animate and --render require exactly one --day",
+            parse("--day 13,14 --animate").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let options = parse("--render out.svg --day 13").unwrap();
+        assert_eq!(Some("out.svg".to_string()), options.render);
+        assert_eq!(
+            "unknown image format: out.png",
+            parse("--render out.png --day 13").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        assert!(parse("--explain --format json").unwrap().explain);
+        assert_eq!(
+            "--explain does not support --format csv",
+            parse("--explain --format csv").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let options = parse("--day 15 --param a=65 --param pairs2=").unwrap();
+        assert_eq!(
+            vec![
+                ("a".to_string(), "65".to_string()),
+                ("pairs2".to_string(), "".to_string())
+            ],
+            options.params
+        );
+        assert_eq!("invalid param: a", parse("--day 15 --param a").unwrap_err());
+        assert_eq!(
+            "--param requires exactly one --day",
+            parse("--param a=65").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let options = parse("lint --day 13 input.txt").unwrap();
+        assert!(options.lint);
+        assert_eq!(Some(Source::Path("input.txt".to_string())), options.input);
+        assert_eq!(
+            "lint FILE requires exactly one --day",
+            parse("lint input.txt").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        for args in ["--check --bench", "serve --check", "--list --fuzz"] {
+            let (first, second) = args.split_once(' ').unwrap();
+            assert_eq!(
+                format!("{} and {} cannot be used together", first, second),
+                parse(args).unwrap_err()
+            );
+        }
+        assert_eq!(
+            "--examples and --render cannot be used together",
+            parse("--examples --render out.svg").unwrap_err()
+        );
+        assert!(parse("--animate --render out.svg --day 13").is_ok());
+        assert!(parse("serve repl").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::context::Params;
+use crate::error::{Error, Result};
+use crate::puzzle::unknown_param;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+/// Read by default if it exists.
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+/// The parameters of each day, see `Puzzle::params`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    days: BTreeMap<u8, Params>,
+}
+
+impl Config {
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    /// The parameters given for `day`.
+    pub fn params(&self, day: u8) -> Params {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    /// Sets the parameter `name` of `day`, which must have one.
+    pub fn set(&mut self, day: u8, name: &str, value: &str) -> Result<()> {
+        let known = crate::day(day)
+            .ok_or_else(|| Error::Solve(format!("day {} is not implemented", day)))?
+            .params()
+            .iter()
+            .any(|(param, _)| *param == name);
+        if !known {
+            return Err(unknown_param(day, name));
+        }
+        self.days
+            .entry(day)
+            .or_default()
+            .insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+/// Parses the subset of TOML used by the configuration: a `[dayN]` table
+/// per day holding `name = value` lines, the values being integers or
+/// basic strings. `#` starts a comment.
+///
+/// ```toml
+/// [day15]
+/// a = 65
+/// pairs1 = 40_000_000
+///
+/// [day16]
+/// programs = "abcde"
+/// ```
+pub fn parse_config<R: BufRead>(reader: R) -> Result<Config> {
+    let mut config = Config::new();
+    let mut day = None;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let (line_no, content) = (i + 1, strip_comment(&line).trim());
+        if content.is_empty() {
+            continue;
+        }
+        if let Some(table) = content.strip_prefix('[') {
+            let number = table
+                .strip_suffix(']')
+                .and_then(|table| table.trim().strip_prefix("day"))
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| crate::day(*n).is_some());
+            match number {
+                Some(number) => day = Some(number),
+                None => {
+                    return Err(Error::token(
+                        line_no,
+                        &line,
+                        content,
+                        "expected the table of an implemented day, such as `[day15]`",
+                    ))
+                }
+            }
+            continue;
+        }
+        let (name, value) = content
+            .split_once('=')
+            .ok_or_else(|| Error::token(line_no, &line, content, "expected `name = value`"))?;
+        let (name, value) = (name.trim(), value.trim());
+        let day = day
+            .ok_or_else(|| Error::token(line_no, &line, name, "expected a `[dayN]` table first"))?;
+        if config.params(day).contains_key(name) {
+            return Err(Error::token(line_no, &line, name, "duplicate parameter"));
+        }
+        let value = parse_value(value)
+            .ok_or_else(|| Error::token(line_no, &line, value, "expected a number or a string"))?;
+        config
+            .set(day, name, &value)
+            .map_err(|e| Error::token(line_no, &line, name, &e.to_string()))?;
+    }
+    Ok(config)
+}
+
+/// `line` up to a `#` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '\\' if quoted && !escaped => {
+                escaped = true;
+                continue;
+            }
+            '"' if !escaped => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// An integer, possibly with `_` between its digits, or a basic string
+/// with `\"` and `\\` escapes.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(string) = value.strip_prefix('"') {
+        let string = string.strip_suffix('"')?;
+        let mut result = String::new();
+        let mut chars = string.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next()? {
+                    ch @ ('"' | '\\') => result.push(ch),
+                    _ => return None,
+                },
+                '"' => return None,
+                ch => result.push(ch),
+            }
+        }
+        return Some(result);
+    }
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let valid = !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+        && digits.chars().all(|ch| ch.is_ascii_digit() || ch == '_');
+    valid.then(|| value.trim_start_matches('+').replace('_', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            &b"# colleague's input\n[day15]\na = 65 # seed\nb = +8_921\n\n[ day16 ]\nprograms = \"a#c\\\"de\"\n"[..],
+        )
+        .unwrap();
+        let params = config.params(15);
+        assert_eq!(Some("65"), params.get("a").map(String::as_str));
+        assert_eq!(Some("8921"), params.get("b").map(String::as_str));
+        assert_eq!(
+            Some("a#c\"de"),
+            config.params(16).get("programs").map(String::as_str)
+        );
+        assert!(config.params(17).is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |data: &str| parse_config(data.as_bytes()).unwrap_err().to_string();
+        assert_eq!(
+            "<input>:1:1: expected a `[dayN]` table first (found `a`)",
+            error("a = 1")
+        );
+        assert_eq!(
+            "<input>:1:1: expected the table of an implemented day, such as `[day15]` (found `[day3]`)",
+            error("[day3]")
+        );
+        assert_eq!(
+            "<input>:2:1: day 15 has no parameter `seed` (found `seed`)",
+            error("[day15]\nseed = 1")
+        );
+        assert_eq!(
+            "<input>:2:9: expected a number or a string (found `twelve`)",
+            error("[day17]\nsteps = twelve")
+        );
+        assert_eq!(
+            "<input>:3:1: duplicate parameter (found `a`)",
+            error("[day15]\na = 1\na = 2")
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::error::{Error, Result};
+use crate::explain::{Event, Explain};
+use crate::puzzle::parse_arg;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// `Context::poll` only does any work once per this many iterations.
+pub const POLL_EVERY: u64 = 4096;
+
+/// How far a long running solver loop has come.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub done: u64,
+    /// Number of iterations the loop will run, if known in advance.
+    pub total: Option<u64>,
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.total {
+            Some(total) if total > 0 => write!(
+                f,
+                "{}/{} ({:.0}%)",
+                self.done,
+                total,
+                self.done as f64 * 100.0 / total as f64
+            ),
+            _ => write!(f, "{}", self.done),
+        }
+    }
+}
+
+/// Cancels the contexts it was given to, from any thread.
+#[derive(Debug, Clone, Default)]
+pub struct Canceller(Arc<AtomicBool>);
+
+impl Canceller {
+    pub fn new() -> Canceller {
+        Canceller::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Makes the canceller usable again, for callers that run one solver
+    /// after another such as the REPL.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Values of the parameters of a day by name, as given in the
+/// configuration or on the command line.
+pub type Params = BTreeMap<String, String>;
+
+type ProgressFn = Box<dyn Fn(Progress) + Send + Sync>;
+
+/// Handed to the solvers, which poll it from their long loops so that the
+/// caller can follow their progress and stop them.
+#[derive(Default)]
+pub struct Context {
+    canceller: Canceller,
+    deadline: Option<Instant>,
+    on_progress: Option<(ProgressFn, Duration)>,
+    last_report: Mutex<Option<Instant>>,
+    explain: Option<Explain>,
+    params: Params,
+}
+
+impl Context {
+    pub fn new() -> Context {
+        Context::default()
+    }
+
+    /// Gives up with `Error::Cancelled` once `timeout` has passed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Context {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Gives up with `Error::Cancelled` once `canceller` is cancelled.
+    pub fn with_canceller(mut self, canceller: Canceller) -> Context {
+        self.canceller = canceller;
+        self
+    }
+
+    /// Calls `f` with the progress of the running loop, at most once per
+    /// `interval`.
+    pub fn with_progress<F>(mut self, interval: Duration, f: F) -> Context
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some((Box::new(f), interval));
+        self
+    }
+
+    /// Replaces the defaults of the parameters named in `params`.
+    pub fn with_params(mut self, params: Params) -> Context {
+        self.params = params;
+        self
+    }
+
+    /// Whether a value was given for the parameter `name`.
+    pub fn has_param(&self, name: &str) -> bool {
+        self.params.contains_key(name)
+    }
+
+    /// The value given for the parameter `name`, or `default`.
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> Result<T> {
+        match self.params.get(name) {
+            Some(value) => parse_arg(name, value),
+            None => Ok(default),
+        }
+    }
+
+    /// Collects the events the solvers explain their answers with in
+    /// `explain`.
+    pub fn with_explain(mut self, explain: Explain) -> Context {
+        self.explain = Some(explain);
+        self
+    }
+
+    /// Records a step of the derivation of the answer. `event` is only
+    /// called when someone is listening.
+    pub fn explain<F: FnOnce() -> Event>(&self, event: F) {
+        if let Some(explain) = &self.explain {
+            explain.push(event());
+        }
+    }
+
+    pub fn canceller(&self) -> Canceller {
+        self.canceller.clone()
+    }
+
+    /// Returns `Error::Cancelled` if the caller should stop, on every call,
+    /// for loops with few and slow iterations such as animations.
+    pub fn check(&self) -> Result<()> {
+        let expired = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline);
+        if self.canceller.is_cancelled() || expired {
+            return Err(Error::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Called by solvers on each iteration of a long loop, `done` counting
+    /// the iterations so far. Returns `Error::Cancelled` if the solver
+    /// should stop.
+    pub fn poll(&self, done: u64, total: Option<u64>) -> Result<()> {
+        if !done.is_multiple_of(POLL_EVERY) {
+            return Ok(());
+        }
+        if self.canceller.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        if self.deadline.is_none() && self.on_progress.is_none() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if self.deadline.is_some_and(|deadline| now >= deadline) {
+            return Err(Error::Cancelled);
+        }
+        if let Some((f, interval)) = &self.on_progress {
+            let mut last_report = self.last_report.lock().unwrap();
+            match *last_report {
+                Some(last) if now - last < *interval => {}
+                _ => {
+                    *last_report = Some(now);
+                    f(Progress { done, total });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_cancel() {
+        let ctx = Context::new();
+        assert!(ctx.poll(0, None).is_ok());
+        ctx.canceller().cancel();
+        assert!(ctx.poll(1, None).is_ok());
+        assert!(matches!(ctx.check(), Err(Error::Cancelled)));
+        assert!(matches!(ctx.poll(POLL_EVERY, None), Err(Error::Cancelled)));
+
+        let ctx = Context::new().with_timeout(Duration::from_secs(0));
+        assert!(matches!(ctx.poll(0, None), Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn test_progress() {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let ctx = Context::new().with_progress(Duration::from_secs(3600), move |p| {
+            sender.lock().unwrap().send(p).unwrap();
+        });
+        for i in 0..3 * POLL_EVERY {
+            ctx.poll(i, Some(4 * POLL_EVERY)).unwrap();
+        }
+        let reports: Vec<Progress> = receiver.try_iter().collect();
+        assert_eq!(
+            vec![Progress {
+                done: 0,
+                total: Some(4 * POLL_EVERY)
+            }],
+            reports
+        );
+        assert_eq!(
+            "1/4 (25%)",
+            format!(
+                "{}",
+                Progress {
+                    done: 1,
+                    total: Some(4)
+                }
+            )
+        );
+    }
+}
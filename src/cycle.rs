@@ -0,0 +1,110 @@
+use crate::error::Result;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of the sequence `start, step(start), step(step(start)), ...` of a
+/// deterministic step function, which must eventually repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state that is part of the cycle.
+    pub tail: usize,
+    /// Steps between two visits of the same state.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the first state seen twice, i.e. the number of steps taken
+    /// until a state repeats.
+    pub fn first_repeat(&self) -> usize {
+        self.tail + self.length
+    }
+
+    /// The step, at most `tail + length`, whose state equals the state
+    /// after `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.tail {
+            n
+        } else {
+            self.tail + (n - self.tail) % self.length
+        }
+    }
+}
+
+/// Remembers every state until one repeats, running `step` exactly
+/// `tail + length` times and so keeping `tail + length` states in memory.
+pub fn find<S, F>(start: S, step: F) -> Result<Cycle>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Result<S>,
+{
+    let (cycle, _) = walk(start, None, step)?;
+    Ok(cycle.expect("walk only stops early when given a limit"))
+}
+
+/// The state after `n` steps, skipping the repetitions once a cycle is
+/// found. Keeps every state seen on the way, up to `n` of them if the
+/// sequence does not repeat before.
+pub fn after<S, F>(start: S, n: usize, step: F) -> Result<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Result<S>,
+{
+    let (cycle, mut states) = walk(start, Some(n), step)?;
+    let index = match cycle {
+        Some(cycle) => cycle.reduce(n),
+        None => n,
+    };
+    Ok(states.swap_remove(index))
+}
+
+/// Steps until a state repeats or `limit` steps were taken, returning the
+/// cycle if one was found and the states in order.
+fn walk<S, F>(start: S, limit: Option<usize>, mut step: F) -> Result<(Option<Cycle>, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Result<S>,
+{
+    let mut seen = HashMap::new();
+    seen.insert(start.clone(), 0);
+    let mut states = vec![start];
+    while limit.is_none_or(|limit| states.len() <= limit) {
+        let next = step(&states[states.len() - 1])?;
+        if let Some(tail) = seen.get(&next) {
+            let cycle = Cycle {
+                tail: *tail,
+                length: states.len() - tail,
+            };
+            return Ok((Some(cycle), states));
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+    Ok((None, states))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn step(n: &u32) -> Result<u32> {
+        Ok(if *n == 5 { 3 } else { n + 1 })
+    }
+
+    #[test]
+    fn test_find() {
+        let expected = Cycle { tail: 3, length: 3 };
+        assert_eq!(expected, find(0, step).unwrap());
+        assert_eq!(6, expected.first_repeat());
+        assert_eq!(Cycle { tail: 0, length: 1 }, find(7, |n| Ok(*n)).unwrap());
+    }
+
+    #[test]
+    fn test_after() {
+        assert_eq!(2, after(0, 2, step).unwrap());
+        assert_eq!(5, after(0, 5, step).unwrap());
+        assert_eq!(3, after(0, 6, step).unwrap());
+        assert_eq!(4, after(0, 1_000_000_000, step).unwrap());
+        assert_eq!(0, after(0, 0, step).unwrap());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::explain::Event;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(mut reader: R) -> Result<Vec<u8>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let line = data.trim_end();
+    if line.is_empty() {
+        return Err(Error::parse(1, 1, "", "expected digits"));
+    }
+    line.char_indices()
+        .map(|(i, ch)| {
+            ch.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+                Error::token(1, line, &line[i..i + ch.len_utf8()], "expected a digit")
+            })
+        })
+        .collect()
+}
+
+/// Explains each digit that matches the one at `other`.
+fn explain_match(ctx: &Context, index: usize, other: usize, digit: u8) {
+    ctx.explain(|| {
+        Event::new("match")
+            .with("index", index)
+            .with("other", other)
+            .with("digit", digit)
+    });
+}
+
+pub fn solve1(digits: &[u8], ctx: &Context) -> usize {
+    let mut result = 0;
+    let mut digits1: Vec<u8> = digits.to_vec();
+    digits1.push(digits[0]);
+    for (index, window) in digits1.windows(2).enumerate() {
+        if window[0] == window[1] {
+            explain_match(ctx, index, (index + 1) % digits.len(), window[0]);
+            result += window[0] as usize;
+        }
+    }
+    result
+}
+
+pub fn solve2(digits: &[u8], ctx: &Context) -> usize {
+    let mut result = 0;
+    let cnt = digits.len();
+    let offset = cnt / 2;
+    for (index, digit) in digits.iter().enumerate() {
+        if digits[(index + offset) % cnt] == *digit {
+            explain_match(ctx, index, (index + offset) % cnt, *digit);
+            result += *digit as usize;
+        }
+    }
+    result
+}
+
+/// `size` random digits.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let digits: String = (0..size.max(1))
+        .map(|_| (b'0' + rng.below(10) as u8) as char)
+        .collect();
+    digits + "\n"
+}
+
+pub struct Day1;
+
+impl Puzzle for Day1 {
+    type Input = Vec<u8>;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day1_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve1(input, ctx).to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(input, ctx).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_solve1() {
+        use super::*;
+        let digits1 = vec![1, 1, 2, 2];
+        let digits2 = vec![1, 1, 1, 1];
+        let digits3 = vec![1, 2, 3, 4];
+        assert_eq!(3, solve1(&digits1, &Context::new()));
+        assert_eq!(4, solve1(&digits2, &Context::new()));
+        assert_eq!(0, solve1(&digits3, &Context::new()));
+    }
+
+    #[test]
+    fn test_solve2() {
+        use super::*;
+        let digits1 = vec![1, 2, 1, 2];
+        let digits2 = vec![1, 2, 2, 1];
+        let digits3 = vec![1, 2, 3, 4, 2, 5];
+        let digits4 = vec![1, 2, 3, 1, 2, 3];
+        let digits5 = vec![1, 2, 1, 3, 1, 4, 1, 5];
+        assert_eq!(6, solve2(&digits1, &Context::new()));
+        assert_eq!(0, solve2(&digits2, &Context::new()));
+        assert_eq!(4, solve2(&digits3, &Context::new()));
+        assert_eq!(12, solve2(&digits4, &Context::new()));
+        assert_eq!(4, solve2(&digits5, &Context::new()));
+    }
+
+    #[test]
+    fn test_explain() {
+        use super::*;
+        use crate::explain::Explain;
+        let explain = Explain::new();
+        let ctx = Context::new().with_explain(explain.clone());
+        assert_eq!(3, solve1(&[1, 1, 2, 2], &ctx));
+        let events: Vec<String> = explain.take().iter().map(Event::text).collect();
+        assert_eq!(
+            vec![
+                "match index=0 other=1 digit=1",
+                "match index=2 other=3 digit=2"
+            ],
+            events
+        );
+    }
+}
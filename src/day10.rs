@@ -0,0 +1,103 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::knothash;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_lengths<R: BufRead>(mut reader: R) -> Result<Vec<usize>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let line = data.trim();
+    line.split(',')
+        .map(|s| match s.trim().parse::<usize>() {
+            Ok(n) if n <= 256 => Ok(n),
+            _ => Err(Error::token(
+                1,
+                line,
+                s,
+                "expected a length between 0 and 256",
+            )),
+        })
+        .collect()
+}
+
+pub fn solve1(input: &[usize]) -> Vec<usize> {
+    let (result, _) = knothash::hash(input, 1);
+    result
+}
+
+pub fn parse_lengths2<R: BufRead>(mut reader: R) -> Result<Vec<usize>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    data.trim().bytes().map(|b| Ok(b as usize)).collect()
+}
+
+pub fn solve2(input: &[usize]) -> String {
+    let mut lengths = input.to_vec();
+    lengths.extend_from_slice(&[17, 31, 73, 47, 23]);
+    let (_, result) = knothash::hash(&lengths, 64);
+    result
+}
+
+/// `size` lengths between 0 and 256.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let lengths: Vec<String> = (0..size.max(1))
+        .map(|_| rng.below(257).to_string())
+        .collect();
+    lengths.join(",") + "\n"
+}
+
+pub struct Day10;
+
+impl Puzzle for Day10 {
+    type Input = (Vec<usize>, Vec<usize>);
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day10_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        Ok((
+            parse_lengths(data.as_bytes())?,
+            parse_lengths2(data.as_bytes())?,
+        ))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let result = solve1(&input.0);
+        Ok((result[0] * result[1]).to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        Ok(solve2(&input.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve2() {
+        let result = solve2(&[]);
+        assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", result);
+    }
+
+    #[test]
+    fn test_bitxor() {
+        let numbers = [65, 27, 9, 1, 4, 3, 40, 50, 91, 7, 6, 0, 2, 5, 68, 22];
+        let bxor = numbers.iter().fold(0, |acc, n| acc ^ n);
+        assert_eq!(64, bxor);
+    }
+}
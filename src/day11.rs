@@ -0,0 +1,107 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+struct Position(isize, isize, isize);
+
+impl Position {
+    fn origin() -> Position {
+        Position(0, 0, 0)
+    }
+    // distance in hex grid is half of manhattan distance
+    fn distance(&self, other: &Position) -> usize {
+        ((isize::abs(self.0 - other.0)
+            + isize::abs(self.1 - other.1)
+            + isize::abs(self.2 - other.2))
+            / 2) as usize
+    }
+}
+
+pub fn parse_directions<R: BufRead>(mut reader: R) -> Result<Vec<String>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let dirs = data.trim().split(',').map(|s| s.to_string()).collect();
+    Ok(dirs)
+}
+
+pub fn solve(directions: &[String]) -> Result<(usize, usize)> {
+    let mut position = Position::origin();
+    let mut max_distance = 0;
+    let mut distance = 0;
+    for direction in directions {
+        match direction.as_str() {
+            "n" => {
+                position.1 += 1;
+                position.2 -= 1;
+            }
+            "s" => {
+                position.1 -= 1;
+                position.2 += 1;
+            }
+            "ne" => {
+                position.0 += 1;
+                position.2 -= 1;
+            }
+            "sw" => {
+                position.0 -= 1;
+                position.2 += 1;
+            }
+            "se" => {
+                position.0 += 1;
+                position.1 -= 1;
+            }
+            "nw" => {
+                position.0 -= 1;
+                position.1 += 1;
+            }
+            _ => return Err(Error::Solve(format!("Unknown direction: {}", direction))),
+        }
+        distance = position.distance(&Position::origin());
+        if distance > max_distance {
+            max_distance = distance;
+        }
+    }
+    Ok((distance, max_distance))
+}
+
+/// `size` steps in random directions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let steps: Vec<&str> = (0..size.max(1))
+        .map(|_| *rng.choose(&["n", "ne", "se", "s", "sw", "nw"]))
+        .collect();
+    steps.join(",") + "\n"
+}
+
+pub struct Day11;
+
+impl Puzzle for Day11 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day11_input.csv"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_directions(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (distance, _) = solve(input)?;
+        Ok(distance.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (_, max_distance) = solve(input)?;
+        Ok(max_distance.to_string())
+    }
+}
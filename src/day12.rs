@@ -0,0 +1,240 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::graph::Graph;
+use crate::parser::Cursor;
+use crate::puzzle::{parse_arg, unknown_view};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+fn program_id(cursor: &mut Cursor) -> Result<usize> {
+    let id = cursor.integer("a program id")?;
+    cursor.end("expected a single program id")?;
+    Ok(id)
+}
+
+pub fn parse_connections<R: BufRead>(reader: R) -> Result<Graph<usize>> {
+    let mut result = Graph::new();
+    let mut declared = HashSet::new();
+    let mut references = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let (mut key, others) = Cursor::new(i + 1, &line).key_value("<->", "`<->`")?;
+        let key = program_id(&mut key)?;
+        declared.insert(key);
+        result.add_node(key);
+        for mut other in others.separated(",") {
+            let token = other.rest().trim();
+            let num = program_id(&mut other)?;
+            result.add_undirected_edge(key, num);
+            references.push((num, other.error(token, "unknown program id")));
+        }
+    }
+    // Pipes may lead to programs listed further down.
+    match references
+        .into_iter()
+        .find(|(num, _)| !declared.contains(num))
+    {
+        Some((_, e)) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Every malformed line, and every pipe whose other end does not list it
+/// back: pipes go both ways.
+pub fn lint(input: &str) -> Vec<Error> {
+    let mut problems = vec![];
+    let mut lists: HashMap<usize, HashSet<usize>> = HashMap::new();
+    let mut pipes = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let (mut key_cursor, others) = match Cursor::new(i + 1, line).key_value("<->", "`<->`") {
+            Ok(sides) => sides,
+            Err(e) => {
+                problems.push(e);
+                continue;
+            }
+        };
+        let key_token = key_cursor.rest().trim();
+        let key = match program_id(&mut key_cursor) {
+            Ok(key) => key,
+            Err(e) => {
+                problems.push(e);
+                continue;
+            }
+        };
+        if lists.contains_key(&key) {
+            problems.push(key_cursor.error(key_token, "duplicate program id"));
+        }
+        let list = lists.entry(key).or_default();
+        for mut cursor in others.separated(",") {
+            let token = cursor.rest().trim();
+            match program_id(&mut cursor) {
+                Ok(other) => {
+                    list.insert(other);
+                    pipes.push((cursor, token, key, other));
+                }
+                Err(e) => problems.push(e),
+            }
+        }
+    }
+    for (cursor, token, key, other) in pipes {
+        let message = match lists.get(&other) {
+            None => "unknown program id".to_string(),
+            Some(list) if !list.contains(&key) => {
+                format!("program {} does not list {} back", other, key)
+            }
+            _ => continue,
+        };
+        problems.push(cursor.error(token, &message));
+    }
+    problems
+}
+
+/// The groups of programs that can talk to each other.
+pub fn solve(connections: &Graph<usize>) -> Vec<Vec<usize>> {
+    connections.components()
+}
+
+/// `size` programs with symmetric pipes between them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut pipes = vec![vec![]; size];
+    for a in 0..size {
+        for _ in 0..rng.below(3) {
+            let b = rng.below(size);
+            if !pipes[a].contains(&b) {
+                pipes[a].push(b);
+                if a != b {
+                    pipes[b].push(a);
+                }
+            }
+        }
+    }
+    let mut result = String::new();
+    for (a, mut others) in pipes.into_iter().enumerate() {
+        if others.is_empty() {
+            others.push(a);
+        }
+        others.sort_unstable();
+        let others: Vec<String> = others.iter().map(|b| b.to_string()).collect();
+        result += &format!("{} <-> {}\n", a, others.join(", "));
+    }
+    result
+}
+
+pub struct Day12;
+
+impl Puzzle for Day12 {
+    type Input = Graph<usize>;
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day12_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_connections(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let part1 = if input.contains(&0) {
+            input.bfs(0).count()
+        } else {
+            0
+        };
+        Ok(part1.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        Ok(solve(input).len().to_string())
+    }
+
+    fn lint(&self, input: &str, _ctx: &Context) -> Vec<Error> {
+        lint(input)
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        &["groups", "group ID"]
+    }
+
+    fn inspect(
+        &self,
+        input: &Self::Input,
+        view: &str,
+        args: &[&str],
+        _ctx: &Context,
+    ) -> Result<String> {
+        let mut groups = solve(input);
+        for group in groups.iter_mut() {
+            group.sort_unstable();
+        }
+        groups.sort_unstable();
+        let show = |group: &Vec<usize>| {
+            let ids: Vec<String> = group.iter().map(|id| id.to_string()).collect();
+            format!("{} programs: {}", group.len(), ids.join(" "))
+        };
+        match (view, args) {
+            ("groups", []) => Ok(groups.iter().map(show).collect::<Vec<_>>().join("\n")),
+            ("group", [id]) => {
+                let id: usize = parse_arg("id", id)?;
+                groups
+                    .iter()
+                    .find(|group| group.binary_search(&id).is_ok())
+                    .map(show)
+                    .ok_or_else(|| Error::Solve(format!("no program {}", id)))
+            }
+            _ => Err(unknown_view(self.views(), view)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_connections() {
+        let graph =
+            parse_connections(crate::input::open("resources/day12_testdata.txt").unwrap()).unwrap();
+        assert_eq!(2, solve(&graph).len());
+
+        let e = parse_connections(&b"0 <-> 1\n1 <-> 0, 7\n"[..]).unwrap_err();
+        assert_eq!(
+            "<input>:2:10: unknown program id (found `7`)",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_one_way_pipe() {
+        let graph = parse_connections(&b"0 <-> 0\n1 <-> 0\n"[..]).unwrap();
+        let ctx = Context::new();
+        assert_eq!("2", Day12.part1(&graph, &ctx).unwrap());
+        assert_eq!("1", Day12.part2(&graph, &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_lint() {
+        let problems: Vec<String> = lint("0 <-> 1, 2\n1 <-> 1\n2 <-> 0, x\n2 <-> 0\n3 0\n")
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "<input>:3:10: expected a program id (found `x`)",
+                "<input>:4:1: duplicate program id (found `2`)",
+                "<input>:5:1: expected `<->` (found `3 0`)",
+                "<input>:1:7: program 1 does not list 0 back (found `1`)",
+            ],
+            problems
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use crate::animate::{Cell, Colour, Simulation};
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::explain::Event;
+use crate::grid::Point;
+use crate::parser::{self, Cursor};
+use crate::puzzle::{parse_arg, unknown_view};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    pub range: usize,
+    pub pos: usize,
+}
+
+/// `depth: range`, with a range of at least 2 for the scanner to sweep.
+fn parse_scanner(cursor: &mut Cursor) -> Result<(usize, usize)> {
+    let (mut depth, mut range) = cursor.key_value(":", "`depth: range`")?;
+    let range_token = range;
+    let result = (depth.integer("a depth")?, range.integer("a range")?);
+    depth.end("expected a depth")?;
+    range.end("expected a range")?;
+    if result.1 < 2 {
+        return Err(range_token.expected("a range of at least 2"));
+    }
+    Ok(result)
+}
+
+/// Error at the `depth` token if the depth was seen before.
+fn check_depth(depth: Cursor, depths: &mut HashSet<usize>) -> Result<()> {
+    let token = depth.rest().trim();
+    match { depth }.integer::<usize>("a depth") {
+        Ok(value) if !depths.insert(value) => Err(depth.error(token, "duplicate depth")),
+        _ => Ok(()),
+    }
+}
+
+pub fn parse_scanners<R: BufRead>(reader: R) -> Result<HashMap<usize, Scanner>> {
+    let mut depths = HashSet::new();
+    let scanners = parser::lines(reader, |cursor| {
+        let scanner = parse_scanner(&mut { *cursor })?;
+        let (depth, _) = cursor.key_value(":", "`depth: range`")?;
+        check_depth(depth, &mut depths)?;
+        Ok(scanner)
+    })?;
+    Ok(scanners
+        .into_iter()
+        .map(|(depth, range)| (depth, Scanner { range, pos: 0 }))
+        .collect())
+}
+
+/// Every malformed line, including ranges below 2, and duplicate depth.
+pub fn lint(input: &str) -> Vec<Error> {
+    let mut problems = vec![];
+    let mut depths = HashSet::new();
+    for (i, line) in input.lines().enumerate() {
+        if let Ok((depth, _)) = Cursor::new(i + 1, line).key_value(":", "`depth: range`") {
+            if let Err(e) = check_depth(depth, &mut depths) {
+                problems.push(e);
+            }
+        }
+        if let Err(e) = parse_scanner(&mut Cursor::new(i + 1, line)) {
+            problems.push(e);
+        }
+    }
+    problems
+}
+
+fn move_scanners(tick: usize, scanners: &mut HashMap<usize, Scanner>) -> Result<()> {
+    for scanner in scanners.values_mut() {
+        if (tick / (scanner.range - 1)).is_multiple_of(2) {
+            scanner.pos = (scanner.pos + 1) % scanner.range;
+        } else {
+            scanner.pos = (scanner.pos - 1) % scanner.range;
+        }
+    }
+    Ok(())
+}
+
+fn layers_cnt(scanners: &HashMap<usize, Scanner>) -> Result<usize> {
+    let result: usize = *scanners
+        .keys()
+        .max()
+        .ok_or_else(|| Error::solve("0 layers"))?;
+    Ok(result)
+}
+
+pub fn solve1(
+    tick: usize,
+    scanners: &mut HashMap<usize, Scanner>,
+    ctx: &Context,
+) -> Result<(usize, bool)> {
+    let mut severity = 0;
+    let mut caught = false;
+    let layers_cnt = layers_cnt(scanners)?;
+    let mut tick = tick;
+    let mut packet_pos = 0;
+
+    while packet_pos <= layers_cnt {
+        ctx.poll(packet_pos as u64, Some(layers_cnt as u64 + 1))?;
+        if let Some(scanner) = scanners.get(&packet_pos) {
+            if scanner.pos == 0 {
+                ctx.explain(|| {
+                    Event::new("caught")
+                        .with("depth", packet_pos)
+                        .with("range", scanner.range)
+                        .with("tick", tick)
+                        .with("severity", packet_pos * scanner.range)
+                });
+                severity += packet_pos * scanner.range;
+                caught = true;
+            }
+        }
+        move_scanners(tick, scanners)?;
+        packet_pos += 1;
+        tick += 1;
+    }
+
+    Ok((severity, caught))
+}
+
+pub fn solve2(scanners: &mut HashMap<usize, Scanner>, ctx: &Context) -> Result<usize> {
+    let mut result = 0;
+    layers_cnt(scanners)?;
+
+    for delay in 0.. {
+        ctx.poll(delay as u64, None)?;
+        let caught = scanners
+            .iter()
+            .any(|(depth, scanner)| (delay + depth) % (2 * scanner.range - 2) == 0);
+        if !caught {
+            result = delay;
+            break;
+        } else {
+            continue;
+        }
+    }
+    Ok(result)
+}
+
+/// Position of a scanner of `range` at `time`, sweeping down and back up.
+fn scanner_position(range: usize, time: usize) -> usize {
+    if range < 2 {
+        return 0;
+    }
+    let period = 2 * (range - 1);
+    let offset = time % period;
+    offset.min(period - offset)
+}
+
+/// The trip of the packet through the firewall, one layer per tick, the
+/// scanners sweeping along.
+pub struct Animation {
+    ranges: HashMap<usize, usize>,
+    layers: usize,
+    delay: usize,
+    depth: usize,
+    severity: usize,
+    caught: usize,
+}
+
+impl Animation {
+    /// The packet leaves after `delay` picoseconds.
+    pub fn new(scanners: &HashMap<usize, Scanner>, delay: usize) -> Animation {
+        let ranges: HashMap<usize, usize> = scanners.iter().map(|(d, s)| (*d, s.range)).collect();
+        let mut animation = Animation {
+            layers: ranges.keys().copied().max().unwrap_or(0),
+            ranges,
+            delay,
+            depth: 0,
+            severity: 0,
+            caught: 0,
+        };
+        animation.check();
+        animation
+    }
+
+    fn scanner_at(&self, depth: usize) -> Option<usize> {
+        let range = *self.ranges.get(&depth)?;
+        Some(scanner_position(range, self.delay + self.depth))
+    }
+
+    fn check(&mut self) {
+        if self.scanner_at(self.depth) == Some(0) {
+            self.severity += self.depth * self.ranges[&self.depth];
+            self.caught += 1;
+        }
+    }
+}
+
+impl Simulation for Animation {
+    fn step(&mut self) -> bool {
+        if self.depth >= self.layers {
+            return false;
+        }
+        self.depth += 1;
+        self.check();
+        true
+    }
+
+    fn cell(&self, point: Point) -> Cell {
+        if point.x < 0 || point.y < 0 {
+            return Cell::plain(' ');
+        }
+        let (depth, row) = (point.x as usize, point.y as usize);
+        let scanner = self.scanner_at(depth);
+        if point == self.actor().unwrap() {
+            return match scanner {
+                Some(0) => Cell::coloured('X', Colour::Red),
+                _ => Cell::coloured('*', Colour::Green),
+            };
+        }
+        match self.ranges.get(&depth) {
+            Some(_) if scanner == Some(row) => Cell::coloured('S', Colour::Yellow),
+            Some(range) if row < *range => Cell::plain('.'),
+            None if row == 0 && depth <= self.layers => Cell::plain('-'),
+            _ => Cell::plain(' '),
+        }
+    }
+
+    fn bounds(&self) -> (Point, Point) {
+        let rows = self.ranges.values().copied().max().unwrap_or(1);
+        (
+            Point::default(),
+            Point::new(self.layers as isize, rows as isize - 1),
+        )
+    }
+
+    fn actor(&self) -> Option<Point> {
+        Some(Point::new(self.depth as isize, 0))
+    }
+
+    fn status(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("delay", self.delay.to_string()),
+            ("depth", self.depth.to_string()),
+            ("severity", self.severity.to_string()),
+            ("caught", self.caught.to_string()),
+        ]
+    }
+}
+
+/// `size` scanner layers at increasing depths.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        result += &format!("{}: {}\n", depth, rng.range(2, 20));
+        depth += rng.range(1, 3);
+    }
+    result
+}
+
+pub struct Day13;
+
+impl Puzzle for Day13 {
+    type Input = HashMap<usize, Scanner>;
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day13_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_scanners(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let (severity, _) = solve1(0, &mut input.clone(), ctx)?;
+        Ok(severity.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(&mut input.clone(), ctx)?.to_string())
+    }
+
+    /// Part 2 shows the trip with the smallest safe delay.
+    fn animation(
+        &self,
+        input: &Self::Input,
+        part: u8,
+        ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        let delay = match part {
+            1 => 0,
+            _ => solve2(&mut input.clone(), ctx)?,
+        };
+        Ok(Some(Box::new(Animation::new(input, delay))))
+    }
+
+    fn lint(&self, input: &str, _ctx: &Context) -> Vec<Error> {
+        lint(input)
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        &["scanners TICK"]
+    }
+
+    fn inspect(
+        &self,
+        input: &Self::Input,
+        view: &str,
+        args: &[&str],
+        _ctx: &Context,
+    ) -> Result<String> {
+        let tick: usize = match (view, args) {
+            ("scanners", [tick]) => parse_arg("tick", tick)?,
+            _ => return Err(unknown_view(self.views(), view)),
+        };
+        let mut depths: Vec<&usize> = input.keys().collect();
+        depths.sort_unstable();
+        let lines: Vec<String> = depths
+            .into_iter()
+            .map(|depth| {
+                let range = input[depth].range;
+                format!(
+                    "depth {}: range {}, position {}",
+                    depth,
+                    range,
+                    scanner_position(range, tick)
+                )
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let mut scanners =
+            parse_scanners(crate::input::open("resources/day13_testdata.txt").unwrap()).unwrap();
+        let (severity, _) = solve1(0, &mut scanners, &Context::new()).unwrap();
+        assert_eq!(24, severity);
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut scanners =
+            parse_scanners(crate::input::open("resources/day13_testdata.txt").unwrap()).unwrap();
+        assert_eq!(10, solve2(&mut scanners, &Context::new()).unwrap())
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let mut scanners = parse_scanners("1000000000000: 2\n".as_bytes()).unwrap();
+        let ctx = Context::new().with_timeout(std::time::Duration::from_secs(0));
+        assert!(matches!(
+            solve1(0, &mut scanners, &ctx),
+            Err(Error::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_animation() {
+        let scanners =
+            parse_scanners(crate::input::open("resources/day13_testdata.txt").unwrap()).unwrap();
+        let mut animation = Animation::new(&scanners, 0);
+        while animation.step() {}
+        assert_eq!(("severity", "24".to_string()), animation.status()[2]);
+        let mut animation = Animation::new(&scanners, 10);
+        while animation.step() {}
+        assert_eq!(("caught", "0".to_string()), animation.status()[3]);
+    }
+
+    #[test]
+    fn test_animation_cancelled() {
+        let scanners = parse_scanners("0: 2\n1: 2\n".as_bytes()).unwrap();
+        let ctx = Context::new().with_timeout(std::time::Duration::from_secs(0));
+        assert!(matches!(
+            Day13.animation(&scanners, 2, &ctx),
+            Err(Error::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse_scanners("0: 3\n1 2\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:2:1: expected `depth: range` (found `1 2`)",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_short_range() {
+        let e = parse_scanners("0: 1\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:1:4: expected a range of at least 2 (found `1`)",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_depth() {
+        let e = parse_scanners("0: 3\n1: 2\n 0: 4\n".as_bytes()).unwrap_err();
+        assert_eq!("<input>:3:2: duplicate depth (found `0`)", e.to_string());
+    }
+
+    #[test]
+    fn test_lint() {
+        let problems: Vec<String> = lint("0: 3\n1: 1\n2 2\n0: 0\n")
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "<input>:2:4: expected a range of at least 2 (found `1`)",
+                "<input>:3:1: expected `depth: range` (found `2 2`)",
+                "<input>:4:1: duplicate depth (found `0`)",
+                "<input>:4:4: expected a range of at least 2 (found `0`)",
+            ],
+            problems
+        );
+    }
+}
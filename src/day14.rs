@@ -0,0 +1,252 @@
+use crate::animate::{Cell, Colour, Simulation};
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::graph;
+use crate::grid::{Grid, Point};
+use crate::knothash;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+fn hex2bin(input: &str) -> Result<String> {
+    let mut result = String::from("");
+
+    for ch in input.trim().chars() {
+        let i: u32 = ch
+            .to_digit(16)
+            .ok_or_else(|| Error::Solve(format!("invalid hex digit: {}", ch)))?;
+        result.push_str(&format!("{:04b}", i));
+    }
+    Ok(result)
+}
+
+pub fn build_grid(key: &str) -> Result<Grid<bool>> {
+    let mut result = Grid::new(128, 128, false);
+    for i in 0..128 {
+        let key = format!("{}-{}", key, i);
+
+        let mut hash_input: Vec<usize> = key.bytes().map(|b| b as usize).collect();
+        hash_input.extend_from_slice(&[17, 31, 73, 47, 23]);
+        let (_, hash) = knothash::hash(&hash_input, 64);
+        let bin = hex2bin(&hash)?;
+        bin.chars().enumerate().for_each(|(j, ch)| {
+            result[Point::new(j as isize, i)] = ch == '1';
+        });
+    }
+    Ok(result)
+}
+
+/// Number of used squares.
+pub fn solve1(grid: &Grid<bool>) -> usize {
+    grid.iter().filter(|(_, used)| **used).count()
+}
+
+/// Number of regions of adjacent used squares.
+pub fn solve2(grid: &Grid<bool>) -> Result<usize> {
+    let used = |point: &Point| grid.get(*point) == Some(&true);
+    let squares = grid.iter().map(|(point, _)| point).filter(used);
+    let regions = graph::components(squares, |point| point.neighbours4().filter(used));
+    Ok(regions.len())
+}
+
+/// The flood fill of the regions, one square per tick.
+pub struct Animation {
+    grid: Grid<bool>,
+    regions: Grid<Option<usize>>,
+    queue: VecDeque<Point>,
+    /// Index of the next square to look at for a new region.
+    scan: usize,
+    count: usize,
+    filled: usize,
+    last: Option<Point>,
+}
+
+impl Animation {
+    pub fn new(grid: Grid<bool>) -> Animation {
+        Animation {
+            regions: Grid::new(grid.width(), grid.height(), None),
+            grid,
+            queue: VecDeque::new(),
+            scan: 0,
+            count: 0,
+            filled: 0,
+            last: None,
+        }
+    }
+
+    fn is_new(&self, point: Point) -> bool {
+        self.grid.get(point) == Some(&true) && self.regions[point].is_none()
+    }
+
+    /// Starts filling the next region, returning false if there is none.
+    fn next_region(&mut self) -> bool {
+        let width = self.grid.width().max(1);
+        while self.scan < self.grid.width() * self.grid.height() {
+            let point = Point::new((self.scan % width) as isize, (self.scan / width) as isize);
+            self.scan += 1;
+            if self.is_new(point) {
+                self.regions[point] = Some(self.count);
+                self.count += 1;
+                self.queue.push_back(point);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Simulation for Animation {
+    fn step(&mut self) -> bool {
+        if self.queue.is_empty() && !self.next_region() {
+            return false;
+        }
+        let point = self.queue.pop_front().unwrap();
+        let region = self.regions[point];
+        for next in point.neighbours4() {
+            if self.is_new(next) {
+                self.regions[next] = region;
+                self.queue.push_back(next);
+            }
+        }
+        self.filled += 1;
+        self.last = Some(point);
+        true
+    }
+
+    fn cell(&self, point: Point) -> Cell {
+        match (self.grid.get(point), self.regions.get(point)) {
+            (Some(true), Some(Some(region))) => {
+                Cell::coloured('#', Colour::CYCLE[region % Colour::CYCLE.len()])
+            }
+            (Some(true), _) => Cell::plain('#'),
+            (Some(false), _) => Cell::plain('.'),
+            (None, _) => Cell::plain(' '),
+        }
+    }
+
+    fn bounds(&self) -> (Point, Point) {
+        let corner = Point::new(self.grid.width() as isize, self.grid.height() as isize);
+        (Point::default(), corner - Point::new(1, 1))
+    }
+
+    fn actor(&self) -> Option<Point> {
+        self.last
+    }
+
+    fn status(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("regions", self.count.to_string()),
+            ("filled", self.filled.to_string()),
+        ]
+    }
+}
+
+pub fn parse_key<R: BufRead>(mut reader: R) -> Result<String> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let key = data.trim();
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return Err(Error::token(1, key, key, "expected a single key"));
+    }
+    Ok(key.to_string())
+}
+
+/// A key of `size` lowercase letters.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    rng.word(size.max(1)) + "\n"
+}
+
+pub struct Day14;
+
+impl Puzzle for Day14 {
+    type Input = String;
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day14_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_key(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let grid = build_grid(&ctx.param("key", input.clone())?)?;
+        Ok(solve1(&grid).to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let grid = build_grid(&ctx.param("key", input.clone())?)?;
+        Ok(solve2(&grid)?.to_string())
+    }
+
+    fn animation(
+        &self,
+        input: &Self::Input,
+        _part: u8,
+        ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        let grid = build_grid(&ctx.param("key", input.clone())?)?;
+        Ok(Some(Box::new(Animation::new(grid))))
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[("key", "key the rows are hashed with (default: the input)")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_hex2bin() {
+        assert_eq!("0001", &hex2bin("1").unwrap());
+        assert_eq!("0000", &hex2bin("0").unwrap());
+        assert_eq!("1110", &hex2bin("e").unwrap());
+        assert_eq!("1111", &hex2bin("f").unwrap());
+        assert_eq!("0111", &hex2bin("7").unwrap());
+        assert_eq!("11010100111101110110101111011100101111111000001110001111100001000001011011001100111110101000101111000110110100011111100111100110", &hex2bin("d4f76bdcbf838f8416ccfa8bc6d1f9e6").unwrap());
+    }
+
+    #[test]
+    fn test_part1() {
+        let grid = build_grid("flqrgnkx").unwrap();
+        assert_eq!(8108, grid.iter().filter(|(_, used)| **used).count());
+    }
+
+    #[test]
+    fn test_solve() {
+        let grid = build_grid("flqrgnkx").unwrap();
+        assert_eq!(8108, solve1(&grid));
+        assert_eq!(1242, solve2(&grid).unwrap());
+    }
+
+    #[test]
+    fn test_animation() {
+        let mut params = crate::context::Params::new();
+        params.insert("key".to_string(), "flqrgnkx".to_string());
+        let ctx = Context::new().with_params(params);
+        let mut animation = Day14
+            .animation(&"ignored".to_string(), 1, &ctx)
+            .unwrap()
+            .unwrap();
+        while animation.step() {}
+        assert_eq!(
+            vec![
+                ("regions", "1242".to_string()),
+                ("filled", "8108".to_string())
+            ],
+            animation.status()
+        );
+    }
+}
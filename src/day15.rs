@@ -0,0 +1,183 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::puzzle::{parse_arg, unknown_view};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+use std::thread;
+
+pub fn parse_seeds<R: BufRead>(mut reader: R) -> Result<(u64, u64)> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let seeds = data
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let seed = line.split_whitespace().last().unwrap_or("");
+            match seed.parse::<u64>() {
+                Ok(seed) if seed < 2_147_483_647 => Ok(seed),
+                _ => Err(Error::token(i + 1, line, seed, "expected a seed")),
+            }
+        })
+        .collect::<Result<Vec<u64>>>()?;
+    match seeds.as_slice() {
+        [a, b] => Ok((*a, *b)),
+        _ => Err(Error::parse(
+            seeds.len() + 1,
+            1,
+            "",
+            &format!("expected 2 generators, found {}", seeds.len()),
+        )),
+    }
+}
+
+pub fn solve1(seed_a: u64, seed_b: u64, cnt: u64, ctx: &Context) -> Result<u64> {
+    let div = 2_147_483_647;
+    let mut result = 0;
+    let mut a = seed_a;
+    let mut b = seed_b;
+
+    for i in 0..cnt {
+        ctx.poll(i, Some(cnt))?;
+        a = (16807 * a) % div;
+        b = (48271 * b) % div;
+        if (a as u16) == (b as u16) {
+            result += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn solve2(prev_a: u64, prev_b: u64, cnt: u64, ctx: &Context) -> Result<u64> {
+    let div = 2_147_483_647;
+    let mut result = 0;
+    let mut a = prev_a;
+    let mut b = prev_b;
+
+    for i in 0..cnt {
+        ctx.poll(i, Some(cnt))?;
+        let a_handle = thread::spawn(move || loop {
+            a = (16807 * a) % div;
+            if a.is_multiple_of(4) {
+                return a;
+            }
+        });
+
+        loop {
+            b = (48271 * b) % div;
+            if b.is_multiple_of(8) {
+                break;
+            }
+        }
+        a = a_handle.join().unwrap();
+        if (a as u16) == (b as u16) {
+            result += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// Two generator seeds, `size` is ignored.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    format!(
+        "Generator A starts with {}\nGenerator B starts with {}\n",
+        rng.range(1, 2_147_483_646),
+        rng.range(1, 2_147_483_646)
+    )
+}
+
+pub struct Day15;
+
+impl Puzzle for Day15 {
+    type Input = (u64, u64);
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day15_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_seeds(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let (a, b) = seeds(input, ctx)?;
+        let pairs = ctx.param("pairs1", 40_000_000)?;
+        Ok(solve1(a, b, pairs, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let (a, b) = seeds(input, ctx)?;
+        let pairs = ctx.param("pairs2", 5_000_000)?;
+        Ok(solve2(a, b, pairs, ctx)?.to_string())
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        &["values N"]
+    }
+
+    /// The first `N` values of both generators of part 1, `*` marking the
+    /// pairs whose lowest 16 bits match.
+    fn inspect(
+        &self,
+        input: &Self::Input,
+        view: &str,
+        args: &[&str],
+        ctx: &Context,
+    ) -> Result<String> {
+        let n: usize = match (view, args) {
+            ("values", [n]) => parse_arg("N", n)?,
+            _ => return Err(unknown_view(self.views(), view)),
+        };
+        let (mut a, mut b) = seeds(input, ctx)?;
+        let mut lines = vec![];
+        for _ in 0..n {
+            a = (16807 * a) % 2_147_483_647;
+            b = (48271 * b) % 2_147_483_647;
+            let mark = if a as u16 == b as u16 { " *" } else { "" };
+            lines.push(format!("{:>10} {:>10}{}", a, b, mark));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("a", "seed of generator A (default: from the input)"),
+            ("b", "seed of generator B (default: from the input)"),
+            ("pairs1", "pairs judged in part 1 (default: 40000000)"),
+            ("pairs2", "pairs judged in part 2 (default: 5000000)"),
+        ]
+    }
+}
+
+/// The seeds of the input, unless replaced by the parameters `a` and `b`.
+fn seeds(input: &(u64, u64), ctx: &Context) -> Result<(u64, u64)> {
+    let seed = |name, default| match ctx.param(name, default)? {
+        seed if seed < 2_147_483_647 => Ok(seed),
+        seed => Err(Error::Solve(format!("invalid {}: {}", name, seed))),
+    };
+    Ok((seed("a", input.0)?, seed("b", input.1)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(1, solve1(65, 8921, 5, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(309, solve2(65, 8921, 5_000_000, &Context::new()).unwrap());
+    }
+}
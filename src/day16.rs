@@ -0,0 +1,257 @@
+use crate::context::Context;
+use crate::cycle;
+use crate::error::{Error, Result};
+use crate::parser::Cursor;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+#[derive(Debug)]
+pub enum Move {
+    Spin(usize),
+    Exchange(usize, usize),
+    Partner(char, char),
+}
+
+fn position(cursor: &mut Cursor) -> Result<usize> {
+    let position = cursor.integer("a position")?;
+    cursor.end("expected two operands")?;
+    Ok(position)
+}
+
+fn program(cursor: &mut Cursor) -> Result<char> {
+    let program = cursor.letter("a program name")?;
+    cursor.end("expected two operands")?;
+    Ok(program)
+}
+
+/// `sN`, `xA/B` or `pA/B`.
+fn parse_move(cursor: &mut Cursor) -> Result<Move> {
+    let result = match cursor.one_of(&["s", "x", "p"], "a dance move")? {
+        "s" => Move::Spin(cursor.integer("a position")?),
+        "x" => {
+            let (mut a, mut b) = cursor.key_value("/", "two operands")?;
+            Move::Exchange(position(&mut a)?, position(&mut b)?)
+        }
+        _ => {
+            let (mut a, mut b) = cursor.key_value("/", "two operands")?;
+            Move::Partner(program(&mut a)?, program(&mut b)?)
+        }
+    };
+    cursor.end("expected `,`")?;
+    Ok(result)
+}
+
+/// The operands of the move at `cursor`, which parses.
+fn operand_tokens<'a>(mut cursor: Cursor<'a>) -> Vec<&'a str> {
+    let _ = cursor.one_of(&["s", "x", "p"], "a dance move");
+    match cursor.key_value("/", "two operands") {
+        Ok((a, b)) => vec![a.rest().trim(), b.rest().trim()],
+        Err(_) => vec![cursor.rest().trim()],
+    }
+}
+
+pub fn parse_moves<R: BufRead>(mut reader: R) -> Result<Vec<Move>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    Cursor::new(1, data.trim()).list(",", parse_move)
+}
+
+/// Every malformed move, and every spin, exchange or partner that does not
+/// fit the line of `programs`.
+pub fn lint(input: &str, programs: &[char]) -> Vec<Error> {
+    let len = programs.len();
+    let mut problems = vec![];
+    for cursor in Cursor::new(1, input.trim()).separated(",") {
+        let (valid, message) = match parse_move(&mut { cursor }) {
+            Ok(Move::Spin(size)) => (
+                vec![size <= len],
+                format!("expected a spin of at most {}", len),
+            ),
+            Ok(Move::Exchange(a, b)) => (
+                vec![a < len, b < len],
+                format!("expected a position below {}", len),
+            ),
+            Ok(Move::Partner(a, b)) => (
+                vec![programs.contains(&a), programs.contains(&b)],
+                "expected one of the programs".to_string(),
+            ),
+            Err(e) => {
+                problems.push(e);
+                continue;
+            }
+        };
+        for (token, valid) in operand_tokens(cursor).into_iter().zip(valid) {
+            if !valid {
+                problems.push(cursor.error(token, &message));
+            }
+        }
+    }
+    problems
+}
+
+pub fn solve1(moves: &[Move], programs: &mut [char]) {
+    for m in moves.iter() {
+        match m {
+            Move::Spin(i) => {
+                programs.rotate_right(*i);
+            }
+            Move::Exchange(i, j) => {
+                programs.swap(*i, *j);
+            }
+            Move::Partner(ch1, ch2) => {
+                for ch in programs.iter_mut() {
+                    if ch == ch1 {
+                        *ch = *ch2
+                    } else if ch == ch2 {
+                        *ch = *ch1
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Dances `cnt` times, skipping the repeats once the order of the
+/// programs loops.
+pub fn solve2(moves: &[Move], programs: &mut [char], cnt: usize, ctx: &Context) -> Result<()> {
+    let mut dances = 0;
+    let result = cycle::after(programs.to_vec(), cnt, |programs| {
+        ctx.poll(dances, Some(cnt as u64))?;
+        dances += 1;
+        let mut programs = programs.clone();
+        solve1(moves, &mut programs);
+        Ok(programs)
+    })?;
+    programs.clone_from_slice(&result);
+    Ok(())
+}
+
+/// `size` dance moves for 16 programs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let moves: Vec<String> = (0..size.max(1))
+        .map(|_| match rng.below(3) {
+            0 => format!("s{}", rng.range(1, 15)),
+            1 => format!("x{}/{}", rng.below(16), rng.below(16)),
+            _ => {
+                let a = (b'a' + rng.below(16) as u8) as char;
+                let b = (b'a' + rng.below(16) as u8) as char;
+                format!("p{}/{}", a, b)
+            }
+        })
+        .collect();
+    moves.join(",") + "\n"
+}
+
+const PROGRAMS: &str = "abcdefghijklmnop";
+
+/// The starting line of programs, long enough for every position the
+/// moves name.
+fn programs(moves: &[Move], ctx: &Context) -> Result<Vec<char>> {
+    let line = ctx.param("programs", PROGRAMS.to_string())?;
+    let programs: Vec<char> = line.chars().collect();
+    let valid = moves.iter().all(|m| match m {
+        Move::Spin(i) => *i <= programs.len(),
+        Move::Exchange(i, j) => *i < programs.len() && *j < programs.len(),
+        Move::Partner(_, _) => true,
+    });
+    if programs.is_empty() || !valid {
+        return Err(Error::Solve(format!("invalid programs: {}", line)));
+    }
+    Ok(programs)
+}
+
+pub struct Day16;
+
+impl Puzzle for Day16 {
+    type Input = Vec<Move>;
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day16_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_moves(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let mut programs = programs(input, ctx)?;
+        solve1(input, &mut programs);
+        Ok(programs.iter().collect())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let mut programs = programs(input, ctx)?;
+        solve2(
+            input,
+            &mut programs,
+            ctx.param("dances", 1_000_000_000)?,
+            ctx,
+        )?;
+        Ok(programs.iter().collect())
+    }
+
+    fn lint(&self, input: &str, ctx: &Context) -> Vec<Error> {
+        match ctx.param("programs", PROGRAMS.to_string()) {
+            Ok(programs) => lint(input, &programs.chars().collect::<Vec<char>>()),
+            Err(e) => vec![e],
+        }
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "programs",
+                "the line of programs before dancing (default: abcdefghijklmnop)",
+            ),
+            ("dances", "dances in part 2 (default: 1000000000)"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve2() {
+        let moves =
+            parse_moves(crate::input::open("resources/day16_testdata.txt").unwrap()).unwrap();
+        let dance = |cnt| {
+            let mut programs: Vec<char> = "abcde".chars().collect();
+            solve2(&moves, &mut programs, cnt, &Context::new()).unwrap();
+            programs.into_iter().collect::<String>()
+        };
+        assert_eq!("baedc", dance(1));
+        assert_eq!("ceadb", dance(2));
+        assert_eq!(dance(2), dance(2 + 4 * 1000));
+    }
+
+    #[test]
+    fn test_lint() {
+        let programs: Vec<char> = "abcde".chars().collect();
+        let problems: Vec<String> = lint("s1,x3/5,s6,pa/z,x,x9/7\n", &programs)
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "<input>:1:7: expected a position below 5 (found `5`)",
+                "<input>:1:10: expected a spin of at most 5 (found `6`)",
+                "<input>:1:15: expected one of the programs (found `z`)",
+                "<input>:1:18: expected two operands (found nothing)",
+                "<input>:1:20: expected a position below 5 (found `9`)",
+                "<input>:1:22: expected a position below 5 (found `7`)",
+            ],
+            problems
+        );
+    }
+}
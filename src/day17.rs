@@ -0,0 +1,143 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::puzzle::{parse_arg, unknown_view};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn solve1(steps: usize, last_val: usize, ctx: &Context) -> Result<(Vec<usize>, usize)> {
+    let mut buf: Vec<usize> = vec![0];
+    let mut curpos = 0;
+
+    for i in 1..=last_val {
+        ctx.poll(i as u64, Some(last_val as u64))?;
+        curpos = 1 + (curpos + steps % buf.len()) % buf.len();
+        buf.insert(curpos, i);
+    }
+
+    Ok((buf, curpos))
+}
+
+pub fn solve2(steps: usize, last_val: usize, ctx: &Context) -> Result<usize> {
+    let mut curpos = 0;
+    let mut result = 0;
+
+    for i in 1..=last_val {
+        ctx.poll(i as u64, Some(last_val as u64))?;
+        curpos = (curpos + steps % i + 1) % i;
+        if curpos == 0 {
+            result = i;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn parse_steps<R: BufRead>(mut reader: R) -> Result<usize> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let steps = data.trim();
+    steps
+        .parse::<usize>()
+        .map_err(|_| Error::token(1, steps, steps, "expected a step count"))
+}
+
+/// A step count up to `size`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    format!("{}\n", rng.range(1, size.max(1) as i64))
+}
+
+pub struct Day17;
+
+impl Puzzle for Day17 {
+    type Input = usize;
+
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day17_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_steps(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let steps = ctx.param("steps", *input)?;
+        let (buf, curpos) = solve1(steps, ctx.param("insertions1", 2017)?, ctx)?;
+        Ok(buf[(curpos + 1) % buf.len()].to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let steps = ctx.param("steps", *input)?;
+        Ok(solve2(steps, ctx.param("insertions2", 50_000_000)?, ctx)?.to_string())
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        &["buffer N"]
+    }
+
+    /// The buffer after `N` insertions, the current position in brackets.
+    fn inspect(
+        &self,
+        input: &Self::Input,
+        view: &str,
+        args: &[&str],
+        ctx: &Context,
+    ) -> Result<String> {
+        let n = match (view, args) {
+            ("buffer", [n]) => parse_arg("N", n)?,
+            _ => return Err(unknown_view(self.views(), view)),
+        };
+        let (buf, curpos) = solve1(ctx.param("steps", *input)?, n, ctx)?;
+        let values: Vec<String> = buf
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if i == curpos {
+                    format!("({})", value)
+                } else {
+                    value.to_string()
+                }
+            })
+            .collect();
+        Ok(values.join(" "))
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("steps", "steps before each insertion (default: the input)"),
+            ("insertions1", "values inserted in part 1 (default: 2017)"),
+            (
+                "insertions2",
+                "values inserted in part 2 (default: 50000000)",
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let (buf, curpos) = solve1(3, 2017, &Context::new()).unwrap();
+        assert_eq!(638, buf[(curpos + 1) % buf.len()]);
+        assert_eq!(9, solve2(3, 9, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_huge_steps() {
+        let steps = parse_steps(usize::MAX.to_string().as_bytes()).unwrap();
+        let (buf, _) = solve1(steps, 10, &Context::new()).unwrap();
+        assert_eq!(11, buf.len());
+        solve2(steps, 10, &Context::new()).unwrap();
+    }
+}
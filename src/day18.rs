@@ -0,0 +1,485 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::parser::{self, Cursor};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i64),
+    Reg(char),
+}
+
+#[derive(Debug, Clone)]
+pub enum OpCode {
+    Snd(Value),
+    Set(char, Value),
+    Add(char, Value),
+    Mul(char, Value),
+    Mod(char, Value),
+    Rcv(Value),
+    Jgz(Value, Value),
+}
+
+struct Cpu {
+    pc: i64,
+    regs: HashMap<char, i64>,
+    result: Option<i64>,
+}
+
+impl Cpu {
+    fn new() -> Cpu {
+        Cpu {
+            pc: 0,
+            regs: HashMap::new(),
+            result: None,
+        }
+    }
+
+    fn run(&mut self, opcodes: &[OpCode], ctx: &Context) -> Result<Option<i64>> {
+        let mut steps = 0;
+        while self.pc >= 0 && self.pc < opcodes.len() as i64 {
+            ctx.poll(steps, None)?;
+            steps += 1;
+            match &opcodes[self.pc as usize] {
+                OpCode::Snd(val) => {
+                    let x = match val {
+                        Value::Int(i) => i,
+                        Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                    };
+                    self.result = Some(*x);
+                    self.pc += 1;
+                }
+                OpCode::Set(ch, val) => {
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    self.regs.insert(*ch, y);
+                    self.pc += 1;
+                }
+                OpCode::Add(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    let sum = x
+                        .checked_add(y)
+                        .ok_or_else(|| Error::solve("overflow in add"))?;
+                    self.regs.insert(*ch, sum);
+                    self.pc += 1;
+                }
+                OpCode::Mul(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    let product = x
+                        .checked_mul(y)
+                        .ok_or_else(|| Error::solve("overflow in mul"))?;
+                    self.regs.insert(*ch, product);
+                    self.pc += 1;
+                }
+                OpCode::Mod(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    if y == 0 {
+                        return Err(Error::solve("mod by zero"));
+                    }
+                    let rem = x
+                        .checked_rem(y)
+                        .ok_or_else(|| Error::solve("overflow in mod"))?;
+                    self.regs.insert(*ch, rem);
+                    self.pc += 1;
+                }
+                OpCode::Rcv(val) => {
+                    let x = match val {
+                        Value::Int(i) => i,
+                        Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                    };
+                    if *x != 0i64 {
+                        break;
+                    }
+                    self.pc += 1;
+                }
+                OpCode::Jgz(val1, val2) => {
+                    let x = match val1 {
+                        Value::Int(i) => i,
+                        Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                    };
+                    if x > &0 {
+                        let y = match val2 {
+                            Value::Int(i) => i,
+                            Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                        };
+                        self.pc += y;
+                    } else {
+                        self.pc += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(self.result)
+    }
+}
+
+struct Cpu2 {
+    pc: i64,
+    regs: HashMap<char, i64>,
+    result: i64,
+    sender: Sender<i64>,
+    receiver: Receiver<i64>,
+}
+
+impl Cpu2 {
+    fn new(sender: Sender<i64>, receiver: Receiver<i64>, p_val: i64) -> Cpu2 {
+        let mut regs = HashMap::new();
+        regs.insert('p', p_val);
+        Cpu2 {
+            pc: 0,
+            regs,
+            result: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    fn run(&mut self, opcodes: &[OpCode], ctx: &Context) -> Result<i64> {
+        let mut steps = 0;
+        while self.pc >= 0 && self.pc < opcodes.len() as i64 {
+            ctx.poll(steps, None)?;
+            steps += 1;
+            match &opcodes[self.pc as usize] {
+                OpCode::Snd(val) => {
+                    let x = match val {
+                        Value::Int(i) => i,
+                        Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                    };
+                    self.sender
+                        .send(*x)
+                        .map_err(|_| Error::solve("the other program has terminated"))?;
+                    self.result += 1;
+                    self.pc += 1;
+                }
+                OpCode::Set(ch, val) => {
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    self.regs.insert(*ch, y);
+                    self.pc += 1;
+                }
+                OpCode::Add(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    let sum = x
+                        .checked_add(y)
+                        .ok_or_else(|| Error::solve("overflow in add"))?;
+                    self.regs.insert(*ch, sum);
+                    self.pc += 1;
+                }
+                OpCode::Mul(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    let product = x
+                        .checked_mul(y)
+                        .ok_or_else(|| Error::solve("overflow in mul"))?;
+                    self.regs.insert(*ch, product);
+                    self.pc += 1;
+                }
+                OpCode::Mod(ch, val) => {
+                    let x = *self.regs.get(ch).unwrap_or(&0);
+                    let y = match val {
+                        Value::Int(i) => *i,
+                        Value::Reg(ch) => *self.regs.get(ch).unwrap_or(&0),
+                    };
+                    if y == 0 {
+                        return Err(Error::solve("mod by zero"));
+                    }
+                    let rem = x
+                        .checked_rem(y)
+                        .ok_or_else(|| Error::solve("overflow in mod"))?;
+                    self.regs.insert(*ch, rem);
+                    self.pc += 1;
+                }
+                OpCode::Rcv(Value::Reg(ch)) => {
+                    let d = Duration::from_millis(500);
+                    match self.receiver.recv_timeout(d).map_err(|e| e.to_string()) {
+                        Ok(i) => {
+                            self.regs.insert(*ch, i);
+                            self.pc += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                OpCode::Rcv(Value::Int(_)) => return Err(Error::solve("rcv needs a register")),
+                OpCode::Jgz(val1, val2) => {
+                    let x = match val1 {
+                        Value::Int(i) => i,
+                        Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                    };
+                    if x > &0 {
+                        let y = match val2 {
+                            Value::Int(i) => i,
+                            Value::Reg(ch) => self.regs.get(ch).unwrap_or(&0),
+                        };
+                        self.pc += y;
+                    } else {
+                        self.pc += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(self.result)
+    }
+}
+
+fn register(cursor: &mut Cursor) -> Result<char> {
+    cursor.letter("a register")
+}
+
+fn value(cursor: &mut Cursor) -> Result<Value> {
+    match cursor.integer("a number") {
+        Ok(num) => Ok(Value::Int(num)),
+        Err(_) => cursor.letter("a register or a number").map(Value::Reg),
+    }
+}
+
+fn parse_op(cursor: &mut Cursor) -> Result<OpCode> {
+    let op = match cursor.identifier("an instruction")? {
+        "snd" => OpCode::Snd(value(cursor)?),
+        "set" => OpCode::Set(register(cursor)?, value(cursor)?),
+        "add" => OpCode::Add(register(cursor)?, value(cursor)?),
+        "mul" => OpCode::Mul(register(cursor)?, value(cursor)?),
+        "mod" => OpCode::Mod(register(cursor)?, value(cursor)?),
+        "rcv" => OpCode::Rcv(value(cursor)?),
+        "jgz" => OpCode::Jgz(value(cursor)?, value(cursor)?),
+        token => return Err(cursor.error(token, "unknown instruction")),
+    };
+    cursor.end("unexpected operand")?;
+    Ok(op)
+}
+
+pub fn parse_ops<R: BufRead>(reader: R) -> Result<Vec<OpCode>> {
+    parser::lines(reader, parse_op)
+}
+
+/// Every malformed line, such as a register that is not a single letter,
+/// every `mod` by 0, which fails, `jgz` by 0, which never ends, and `rcv`
+/// of a number, which part 2 cannot receive into.
+pub fn lint(input: &str) -> Vec<Error> {
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let cursor = Cursor::new(i + 1, line);
+        let message = match parse_op(&mut { cursor }) {
+            Ok(OpCode::Mod(_, Value::Int(0))) => "expected a modulus other than 0",
+            Ok(OpCode::Jgz(_, Value::Int(0))) => "expected a jump other than 0",
+            Ok(OpCode::Rcv(Value::Int(_))) => "expected a register to receive into",
+            Ok(_) => continue,
+            Err(e) => {
+                problems.push(e);
+                continue;
+            }
+        };
+        let last = line.split_whitespace().last().unwrap_or("");
+        problems.push(cursor.error(last, message));
+    }
+    problems
+}
+
+pub fn solve1(ops: &[OpCode], ctx: &Context) -> Result<i64> {
+    let mut cpu = Cpu::new();
+    cpu.run(ops, ctx)?;
+    cpu.result.ok_or_else(|| Error::solve("nothing was played"))
+}
+
+pub fn solve2(ops: &[OpCode], ctx: &Context) -> Result<i64> {
+    let (sa, ra) = mpsc::channel();
+    let (sb, rb) = mpsc::channel();
+
+    let mut cpu2_a = Cpu2::new(sa, rb, 0);
+    let mut cpu2_b = Cpu2::new(sb, ra, 1);
+
+    thread::scope(|scope| {
+        let t1 = scope.spawn(move || cpu2_a.run(ops, ctx));
+        let t2 = scope.spawn(move || cpu2_b.run(ops, ctx));
+
+        let panicked = |_| Error::solve("program panicked");
+        t1.join().map_err(panicked)??;
+        t2.join().map_err(panicked)?
+    })
+}
+
+/// A program of `size` instructions on registers `a` to `e` and `p`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let registers = ["a", "b", "c", "d", "e", "p"];
+    let value = |rng: &mut Rng| {
+        if rng.chance(50) {
+            rng.choose(&registers).to_string()
+        } else {
+            rng.range(-10, 10).to_string()
+        }
+    };
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let line = match rng.below(7) {
+            0 => format!("snd {}", value(rng)),
+            1 => format!("rcv {}", rng.choose(&registers)),
+            2 => format!("jgz {} {}", value(rng), value(rng)),
+            n => {
+                let op = ["set", "add", "mul", "mod"][n - 3];
+                format!("{} {} {}", op, rng.choose(&registers), value(rng))
+            }
+        };
+        result += &line;
+        result += "\n";
+    }
+    result
+}
+
+pub struct Day18;
+
+impl Puzzle for Day18 {
+    type Input = Vec<OpCode>;
+
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day18_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_ops(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve1(input, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(input, ctx)?.to_string())
+    }
+
+    fn lint(&self, input: &str, _ctx: &Context) -> Vec<Error> {
+        lint(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1() {
+        let ops = parse_ops(crate::input::open("resources/day18_testdata.txt").unwrap()).unwrap();
+        let mut cpu = Cpu::new();
+        cpu.run(&ops, &Context::new()).unwrap();
+
+        assert_eq!(4, cpu.result.unwrap());
+    }
+
+    #[test]
+    fn mod_by_zero() {
+        let ops = parse_ops("set a 1\nmod a b\nsnd a\n".as_bytes()).unwrap();
+        let ctx = Context::new();
+        assert_eq!("mod by zero", solve1(&ops, &ctx).unwrap_err().to_string());
+        assert_eq!("mod by zero", solve2(&ops, &ctx).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn overflow() {
+        let ctx = Context::new();
+        let ops = parse_ops("set a 9223372036854775807\nadd a 1\nsnd a\n".as_bytes()).unwrap();
+        assert_eq!(
+            "overflow in add",
+            solve1(&ops, &ctx).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "overflow in add",
+            solve2(&ops, &ctx).unwrap_err().to_string()
+        );
+        let ops = parse_ops("set a -9223372036854775808\nmul a -1\nsnd a\n".as_bytes()).unwrap();
+        assert_eq!(
+            "overflow in mul",
+            solve1(&ops, &ctx).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "overflow in mul",
+            solve2(&ops, &ctx).unwrap_err().to_string()
+        );
+        let ops = parse_ops("set a -9223372036854775808\nmod a -1\nsnd a\n".as_bytes()).unwrap();
+        assert_eq!(
+            "overflow in mod",
+            solve1(&ops, &ctx).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn rcv_number() {
+        let ops = parse_ops("snd 1\nrcv 5\n".as_bytes()).unwrap();
+        let e = solve2(&ops, &Context::new()).unwrap_err();
+        assert_eq!("rcv needs a register", e.to_string());
+    }
+
+    #[test]
+    fn cancelled() {
+        let ops = parse_ops("set a 1\njgz a 0\n".as_bytes()).unwrap();
+        let ctx = Context::new().with_timeout(Duration::from_millis(100));
+        assert!(matches!(solve1(&ops, &ctx), Err(Error::Cancelled)));
+        assert!(matches!(solve2(&ops, &ctx), Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = parse_ops("set a 1\nadd a\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:2:6: expected a register or a number (found nothing)",
+            e.to_string()
+        );
+        let e = parse_ops("jgz a 2 3".as_bytes()).unwrap_err();
+        assert_eq!("<input>:1:9: unexpected operand (found `3`)", e.to_string());
+    }
+
+    #[test]
+    fn lint_problems() {
+        let problems: Vec<String> = lint("set ab 1\nmod a 0\nsnd 1 2\njgz a 0\nrcv a\nrcv 5\n")
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "<input>:1:5: expected a register (found `ab`)",
+                "<input>:2:7: expected a modulus other than 0 (found `0`)",
+                "<input>:3:7: unexpected operand (found `2`)",
+                "<input>:4:7: expected a jump other than 0 (found `0`)",
+                "<input>:6:5: expected a register to receive into (found `5`)",
+            ],
+            problems
+        );
+    }
+}
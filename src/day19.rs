@@ -0,0 +1,296 @@
+use crate::animate::{Cell, Colour, Simulation};
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::explain::Event;
+use crate::grid::{Direction, Point, SparseGrid};
+use crate::normalize::Normalize;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+/// The path, with its letters, and where it enters the map.
+pub type Map = (SparseGrid<char>, Point);
+
+pub fn parse_map<R: BufRead>(mut reader: R) -> Result<Map> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let first = text.lines().next().unwrap_or("");
+    let start = match first.chars().position(|ch| ch == '|') {
+        Some(x) => Point::new(x as isize, 0),
+        None => return Err(Error::token(1, first, first, "starting position not found")),
+    };
+    let map = SparseGrid::parse(text.as_bytes(), |ch| Some(ch).filter(|ch| *ch != ' '))?;
+    Ok((map, start))
+}
+
+struct Walker {
+    position: Point,
+    direction: Direction,
+}
+
+impl Walker {
+    fn new(start: Point, direction: Direction) -> Self {
+        Walker {
+            position: start,
+            direction,
+        }
+    }
+
+    /// Moves ahead if possible, otherwise turns to the left or right.
+    fn walk(&mut self, map: &SparseGrid<char>) -> Option<Point> {
+        let forward = self.direction;
+        for direction in [forward, forward.turn_left(), forward.turn_right()] {
+            let next = self.position.step(direction);
+            if map.contains(next) {
+                self.position = next;
+                self.direction = direction;
+                return Some(next);
+            }
+        }
+        None
+    }
+}
+
+/// The letters on the path and its length. A path that loops back on
+/// itself runs until `ctx` stops it.
+pub fn solve(map: &SparseGrid<char>, start: Point, ctx: &Context) -> Result<(String, isize)> {
+    let mut result = String::from("");
+    let mut steps = 1;
+
+    let mut walker = Walker::new(start, Direction::Down);
+
+    while let Some(pos) = walker.walk(map) {
+        ctx.poll(steps as u64, None)?;
+        steps += 1;
+        match map.get(pos) {
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                ctx.explain(|| {
+                    Event::new("letter")
+                        .with("letter", *ch)
+                        .with("x", pos.x)
+                        .with("y", pos.y)
+                        .with("step", steps)
+                });
+                result.push(*ch);
+            }
+            _ => {}
+        }
+    }
+    ctx.explain(|| {
+        Event::new("end")
+            .with("x", walker.position.x)
+            .with("y", walker.position.y)
+            .with("steps", steps)
+    });
+
+    Ok((result, steps))
+}
+
+/// The walk along the path, one step per tick, the travelled part
+/// highlighted.
+pub struct Animation {
+    map: SparseGrid<char>,
+    visited: HashSet<Point>,
+    walker: Walker,
+    letters: String,
+    steps: usize,
+}
+
+impl Animation {
+    pub fn new(map: &SparseGrid<char>, start: Point) -> Animation {
+        Animation {
+            map: map.clone(),
+            visited: HashSet::from([start]),
+            walker: Walker::new(start, Direction::Down),
+            letters: String::new(),
+            steps: 1,
+        }
+    }
+}
+
+impl Simulation for Animation {
+    fn step(&mut self) -> bool {
+        match self.walker.walk(&self.map) {
+            Some(pos) => {
+                self.visited.insert(pos);
+                self.steps += 1;
+                match self.map.get(pos) {
+                    Some(ch) if ch.is_ascii_alphabetic() => self.letters.push(*ch),
+                    _ => {}
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cell(&self, point: Point) -> Cell {
+        match self.map.get(point) {
+            _ if point == self.walker.position => Cell::coloured('@', Colour::Green),
+            Some(ch) if ch.is_ascii_alphabetic() => Cell::coloured(*ch, Colour::Yellow),
+            Some(ch) if self.visited.contains(&point) => Cell::coloured(*ch, Colour::Cyan),
+            Some(ch) => Cell::plain(*ch),
+            None => Cell::plain(' '),
+        }
+    }
+
+    fn bounds(&self) -> (Point, Point) {
+        self.map.bounds().unwrap_or_default()
+    }
+
+    fn actor(&self) -> Option<Point> {
+        Some(self.walker.position)
+    }
+
+    fn status(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("steps", self.steps.to_string()),
+            ("letters", self.letters.clone()),
+        ]
+    }
+}
+
+/// A map whose path runs down in `size` segments, with letters along it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const WIDTH: usize = 40;
+    let mut rows: Vec<Vec<u8>> = vec![];
+    let mut letters = (b'A'..=b'Z').cycle();
+    let mut x = rng.below(WIDTH);
+    let put = |rows: &mut Vec<Vec<u8>>, x: usize, y: usize, ch: u8| {
+        while rows.len() <= y {
+            rows.push(vec![b' '; WIDTH]);
+        }
+        rows[y][x] = ch;
+    };
+    let start = x;
+    let mut y = 0;
+    for segment in 0..size.max(1) {
+        let len = rng.range(2, 6) as usize;
+        for _ in 0..len {
+            let ch = if rng.chance(15) {
+                letters.next().unwrap()
+            } else {
+                b'|'
+            };
+            put(&mut rows, x, y, ch);
+            y += 1;
+        }
+        if segment + 1 == size.max(1) {
+            put(&mut rows, x, y, letters.next().unwrap());
+            break;
+        }
+        let target = loop {
+            let target = rng.below(WIDTH);
+            if target != x {
+                break target;
+            }
+        };
+        put(&mut rows, x, y, b'+');
+        let (from, to) = (x.min(target), x.max(target));
+        for column in from + 1..to {
+            let ch = if rng.chance(15) {
+                letters.next().unwrap()
+            } else {
+                b'-'
+            };
+            put(&mut rows, column, y, ch);
+        }
+        put(&mut rows, target, y, b'+');
+        x = target;
+        y += 1;
+    }
+    rows[0][start] = b'|';
+    rows.iter()
+        .map(|row| String::from_utf8_lossy(row).into_owned() + "\n")
+        .collect()
+}
+
+pub struct Day19;
+
+impl Puzzle for Day19 {
+    type Input = Map;
+
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day19_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_map(reader)
+    }
+
+    /// A blank line is a row of the map.
+    fn normalize(&self) -> Normalize {
+        Normalize {
+            skip_blank: false,
+            ..Normalize::default()
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let (map, start) = input;
+        let (part1, _) = solve(map, *start, ctx)?;
+        Ok(part1)
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let (map, start) = input;
+        let (_, part2) = solve(map, *start, ctx)?;
+        Ok(part2.to_string())
+    }
+
+    fn animation(
+        &self,
+        input: &Self::Input,
+        _part: u8,
+        _ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        let (map, start) = input;
+        Ok(Some(Box::new(Animation::new(map, *start))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::Explain;
+
+    #[test]
+    fn test_part1() {
+        let (map, start) =
+            parse_map(crate::input::open("resources/day19_testdata.txt").unwrap()).unwrap();
+        let (part1, steps) = solve(&map, start, &Context::new()).unwrap();
+        assert_eq!("ABCDEF", part1);
+        assert_eq!(38, steps);
+
+        let explain = Explain::new();
+        solve(&map, start, &Context::new().with_explain(explain.clone())).unwrap();
+        let events = explain.take();
+        assert_eq!(7, events.len());
+        assert_eq!("letter letter=A x=5 y=2 step=3", events[0].text());
+        assert_eq!("end x=1 y=3 steps=38", events[6].text());
+
+        let mut animation = Animation::new(&map, start);
+        while animation.step() {}
+        assert_eq!(
+            vec![("steps", "38".to_string()), ("letters", part1)],
+            animation.status()
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        let input = "  |\n  |\n+-+-+\n|   |\n+---+\n";
+        let (map, start) = parse_map(input.as_bytes()).unwrap();
+        let ctx = Context::new().with_timeout(std::time::Duration::from_millis(100));
+        assert!(matches!(solve(&map, start, &ctx), Err(Error::Cancelled)));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::explain::Event;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<Vec<usize>>> {
+    let mut result = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut nums: Vec<usize> = vec![];
+        for s in line.trim().split(',') {
+            let n = s
+                .parse::<usize>()
+                .map_err(|_| Error::token(i + 1, &line, s, "expected a number"))?;
+            nums.push(n);
+        }
+        nums.sort();
+        result.push(nums);
+    }
+    Ok(result)
+}
+
+pub fn solve1(sorted_nums: &[Vec<usize>], ctx: &Context) -> Result<usize> {
+    let mut result = 0;
+    for (row, row_nums) in sorted_nums.iter().enumerate() {
+        let first = row_nums
+            .first()
+            .ok_or_else(|| Error::solve("first not found"))?;
+        let last = row_nums
+            .last()
+            .ok_or_else(|| Error::solve("last not found"))?;
+        ctx.explain(|| {
+            Event::new("row")
+                .with("row", row + 1)
+                .with("min", *first)
+                .with("max", *last)
+                .with("difference", last - first)
+        });
+        result += first.max(last) - first.min(last);
+    }
+    Ok(result)
+}
+
+pub fn solve2(sorted_nums: &[Vec<usize>], ctx: &Context) -> usize {
+    let mut result = 0;
+    for (row, row_nums) in sorted_nums.iter().enumerate() {
+        for i in 0..(row_nums.len() - 1) {
+            let smaller = row_nums[i];
+            for bigger in &row_nums[(i + 1)..] {
+                if (bigger % smaller) == 0 {
+                    ctx.explain(|| {
+                        Event::new("row")
+                            .with("row", row + 1)
+                            .with("dividend", *bigger)
+                            .with("divisor", smaller)
+                            .with("quotient", bigger / smaller)
+                    });
+                    result += bigger / smaller;
+                    break;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// `size` rows of 2 to 16 numbers, each row with one evenly divisible pair.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let small = rng.range(1, 500);
+        let mut row = vec![small, small * rng.range(1, 9)];
+        for _ in 0..rng.below(15) {
+            row.insert(rng.below(row.len() + 1), rng.range(1, 9999));
+        }
+        let row: Vec<String> = row.iter().map(|n| n.to_string()).collect();
+        result += &row.join(",");
+        result += "\n";
+    }
+    result
+}
+
+pub struct Day2;
+
+impl Puzzle for Day2 {
+    type Input = Vec<Vec<usize>>;
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day2_input.csv"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve1(input, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(input, ctx).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_solve1() {
+        use super::*;
+        let data = parse_input(crate::input::open("resources/day2_testdata.csv").unwrap()).unwrap();
+        assert_eq!(18, solve1(&data, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_solve2() {
+        use super::*;
+        let data =
+            parse_input(crate::input::open("resources/day2_testdata2.csv").unwrap()).unwrap();
+        assert_eq!(9, solve2(&data, &Context::new()));
+    }
+}
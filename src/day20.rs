@@ -0,0 +1,177 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::parser::{self, Cursor};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+#[derive(Debug, PartialEq, Ord, PartialOrd, Eq, Hash, Clone)]
+pub struct Particle {
+    pub position: (i64, i64, i64),
+    pub velocity: (i64, i64, i64),
+    pub acceleration: (i64, i64, i64),
+}
+
+/// `name=<x,y,z>`.
+fn vector(cursor: &mut Cursor, name: &str) -> Result<(i64, i64, i64)> {
+    cursor.literal(name)?;
+    cursor.literal("=<")?;
+    let x = cursor.integer("a number")?;
+    cursor.literal(",")?;
+    let y = cursor.integer("a number")?;
+    cursor.literal(",")?;
+    let z = cursor.integer("a number")?;
+    cursor.literal(">")?;
+    Ok((x, y, z))
+}
+
+/// `p=<x,y,z>, v=<x,y,z>, a=<x,y,z>`.
+fn parse_particle(cursor: &mut Cursor) -> Result<Particle> {
+    let position = vector(cursor, "p")?;
+    cursor.literal(",")?;
+    let velocity = vector(cursor, "v")?;
+    cursor.literal(",")?;
+    let acceleration = vector(cursor, "a")?;
+    cursor.end("unexpected token")?;
+    Ok(Particle {
+        position,
+        velocity,
+        acceleration,
+    })
+}
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<Particle>> {
+    parser::lines(reader, parse_particle)
+}
+
+pub fn solve1(particles: &[Particle]) -> usize {
+    let mut particle = 0;
+    let mut min_acceleration = u128::MAX;
+
+    for (i, p) in particles.iter().enumerate() {
+        let (x, y, z) = p.acceleration;
+        let a = x.unsigned_abs() as u128 + y.unsigned_abs() as u128 + z.unsigned_abs() as u128;
+        if a < min_acceleration {
+            min_acceleration = a;
+            particle = i;
+        }
+    }
+
+    particle
+}
+
+fn add(a: (i64, i64, i64), b: (i64, i64, i64)) -> Result<(i64, i64, i64)> {
+    let overflow = || Error::solve("overflow moving a particle");
+    Ok((
+        a.0.checked_add(b.0).ok_or_else(overflow)?,
+        a.1.checked_add(b.1).ok_or_else(overflow)?,
+        a.2.checked_add(b.2).ok_or_else(overflow)?,
+    ))
+}
+
+pub fn solve2(particles: &mut Vec<Particle>, ticks: usize, ctx: &Context) -> Result<usize> {
+    for tick in 0..ticks {
+        ctx.poll(tick as u64, Some(ticks as u64))?;
+        for p in particles.iter_mut() {
+            p.velocity = add(p.velocity, p.acceleration)?;
+            p.position = add(p.position, p.velocity)?;
+        }
+
+        particles.sort_by_key(|p| p.position);
+
+        let mut duplicates = HashSet::new();
+
+        for i in 1..particles.len() {
+            if particles[i - 1].position == particles[i].position {
+                duplicates.insert(particles[i - 1].clone());
+                duplicates.insert(particles[i].clone());
+            }
+        }
+        particles.retain(|p| !duplicates.contains(p));
+    }
+
+    Ok(particles.len())
+}
+
+/// `size` particles.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let mut triple = |limit: i64| {
+            let v: Vec<String> = (0..3)
+                .map(|_| rng.range(-limit, limit).to_string())
+                .collect();
+            v.join(",")
+        };
+        let (p, v, a) = (triple(3000), triple(100), triple(10));
+        result += &format!("p=<{}>, v=<{}>, a=<{}>\n", p, v, a);
+    }
+    result
+}
+
+pub struct Day20;
+
+impl Puzzle for Day20 {
+    type Input = Vec<Particle>;
+
+    fn day(&self) -> u8 {
+        20
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day20_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        Ok(solve1(input).to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let ticks = ctx.param("ticks", 1000)?;
+        Ok(solve2(&mut input.clone(), ticks, ctx)?.to_string())
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[("ticks", "ticks simulated in part 2 (default: 1000)")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let particles = parse_input("p=< 3,0,0>, v=< 2,0,0>, a=<-1,0,0>\n".as_bytes()).unwrap();
+        assert_eq!((-1, 0, 0), particles[0].acceleration);
+        let e = parse_input("p=<1,2,3>, v=<1,2>, a=<0,0,0>".as_bytes()).unwrap_err();
+        assert_eq!("<input>:1:18: expected `,` (found `>,`)", e.to_string());
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let mut particles = parse_input("p=<0,0,0>, v=<1,0,0>, a=<0,0,0>".as_bytes()).unwrap();
+        let ctx = Context::new().with_timeout(std::time::Duration::from_secs(0));
+        let result = solve2(&mut particles, usize::MAX, &ctx);
+        assert!(matches!(result, Err(crate::Error::Cancelled)));
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let input = "p=<0,0,0>, v=<0,0,0>, a=<-9223372036854775808,-9223372036854775808,1>\n\
+                     p=<0,0,0>, v=<9223372036854775807,0,0>, a=<1,0,0>\n";
+        let mut particles = parse_input(input.as_bytes()).unwrap();
+        assert_eq!(1, solve1(&particles));
+        let e = solve2(&mut particles, 10, &Context::new()).unwrap_err();
+        assert_eq!("overflow moving a particle", e.to_string());
+    }
+}
@@ -0,0 +1,387 @@
+use crate::animate::{Cell, Colour, Simulation};
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::grid::{Direction, Grid, Point, SparseGrid};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Clean,
+    Weakened,
+    Infected,
+    Flagged,
+}
+
+#[derive(Clone, Debug)]
+pub struct Virus {
+    position: Point,
+    direction: Direction,
+}
+
+pub fn parse_infections<R: BufRead>(reader: R) -> Result<Grid<Status>> {
+    Grid::parse(reader, "expected `#` or `.`", |ch| match ch {
+        '#' => Some(Status::Infected),
+        '.' => Some(Status::Clean),
+        _ => None,
+    })
+}
+
+/// Every malformed line, and a map that is not square with an odd side,
+/// which has no middle for the virus to start at unless `x` and `y` are
+/// given.
+pub fn lint(input: &str, ctx: &Context) -> Vec<Error> {
+    let mut problems = vec![];
+    let lines: Vec<&str> = input.lines().collect();
+    let width = lines.first().map_or(0, |line| line.chars().count());
+    for (i, line) in lines.iter().enumerate() {
+        if let Err(e) = parse_infections(line.as_bytes()) {
+            problems.push(e.on_line(i + 1));
+        }
+        let len = line.chars().count();
+        if len != width {
+            let token: String = line.chars().skip(width).collect();
+            let message = format!("expected a line of {} characters", width);
+            problems.push(Error::parse(i + 1, width.min(len) + 1, &token, &message));
+        }
+    }
+    if !problems.is_empty() || (ctx.has_param("x") && ctx.has_param("y")) {
+        return problems;
+    }
+    let first = lines.first().copied().unwrap_or("");
+    if lines.len() != width {
+        let message = format!(
+            "expected a square map, not {} lines of {} characters",
+            lines.len(),
+            width
+        );
+        problems.push(Error::token(1, first, first, &message));
+    } else if width.is_multiple_of(2) {
+        let message = "expected an odd side, for the map to have a middle";
+        problems.push(Error::token(1, first, first, message));
+    }
+    problems
+}
+
+/// Middle of the map, where the virus starts.
+pub fn center(infections: &Grid<Status>) -> Point {
+    Point::new(
+        infections.width() as isize / 2,
+        infections.height() as isize / 2,
+    )
+}
+
+/// Where the virus starts, the middle unless given by the parameters `x`
+/// and `y`.
+fn start(infections: &Grid<Status>, ctx: &Context) -> Result<Point> {
+    let center = center(infections);
+    Ok(Point::new(
+        ctx.param("x", center.x)?,
+        ctx.param("y", center.y)?,
+    ))
+}
+
+impl Virus {
+    pub fn new(position: Point, direction: Direction) -> Self {
+        Virus {
+            position,
+            direction,
+        }
+    }
+
+    fn burst(&mut self, infections: &mut SparseGrid<Status>) -> bool {
+        let mut infection = false;
+
+        if let Some(Status::Infected) = infections.get(self.position) {
+            self.direction = self.direction.turn_right();
+            infections.insert(self.position, Status::Clean);
+        } else {
+            self.direction = self.direction.turn_left();
+            infections.insert(self.position, Status::Infected);
+            infection = true;
+        }
+
+        self.position = self.position.step(self.direction);
+
+        infection
+    }
+
+    fn burst2(&mut self, infections: &mut SparseGrid<Status>) -> bool {
+        use Status::*;
+
+        let mut infection = false;
+
+        match infections.get(self.position) {
+            Some(Weakened) => {
+                infections.insert(self.position, Infected);
+                infection = true;
+            }
+            Some(Infected) => {
+                infections.insert(self.position, Flagged);
+                self.direction = self.direction.turn_right();
+            }
+            Some(Flagged) => {
+                infections.insert(self.position, Clean);
+                self.direction = self.direction.reverse();
+            }
+            _ => {
+                infections.insert(self.position, Weakened);
+                self.direction = self.direction.turn_left();
+            }
+        }
+
+        self.position = self.position.step(self.direction);
+
+        infection
+    }
+}
+
+pub fn solve1(
+    mut infections: SparseGrid<Status>,
+    mut virus: Virus,
+    bursts: u64,
+    ctx: &Context,
+) -> Result<isize> {
+    let mut result = 0;
+
+    for i in 0..bursts {
+        ctx.poll(i, Some(bursts))?;
+        if virus.burst(&mut infections) {
+            result += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn solve2(
+    mut infections: SparseGrid<Status>,
+    mut virus: Virus,
+    bursts: u64,
+    ctx: &Context,
+) -> Result<isize> {
+    let mut result = 0;
+
+    for i in 0..bursts {
+        ctx.poll(i, Some(bursts))?;
+        if virus.burst2(&mut infections) {
+            result += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+/// The bursts of the virus, one per tick.
+pub struct Animation {
+    infections: SparseGrid<Status>,
+    virus: Virus,
+    /// Part 2 uses the evolved virus.
+    evolved: bool,
+    bursts: usize,
+    total: usize,
+    infected: usize,
+}
+
+impl Animation {
+    /// `bursts` bursts of the virus of `part`, starting at `start`.
+    pub fn new(infections: &Grid<Status>, start: Point, part: u8, bursts: usize) -> Animation {
+        Animation {
+            infections: infections.clone().into(),
+            virus: Virus::new(start, Direction::Up),
+            evolved: part == 2,
+            bursts: 0,
+            total: bursts,
+            infected: 0,
+        }
+    }
+}
+
+impl Simulation for Animation {
+    fn step(&mut self) -> bool {
+        if self.bursts == self.total {
+            return false;
+        }
+        let infection = if self.evolved {
+            self.virus.burst2(&mut self.infections)
+        } else {
+            self.virus.burst(&mut self.infections)
+        };
+        self.bursts += 1;
+        self.infected += infection as usize;
+        true
+    }
+
+    fn cell(&self, point: Point) -> Cell {
+        if point == self.virus.position {
+            let ch = match self.virus.direction {
+                Direction::Up => '^',
+                Direction::Right => '>',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+            };
+            return Cell::coloured(ch, Colour::Green);
+        }
+        match self.infections.get(point) {
+            Some(Status::Infected) => Cell::coloured('#', Colour::Red),
+            Some(Status::Weakened) => Cell::coloured('W', Colour::Yellow),
+            Some(Status::Flagged) => Cell::coloured('F', Colour::Magenta),
+            _ => Cell::plain('.'),
+        }
+    }
+
+    fn bounds(&self) -> (Point, Point) {
+        self.infections.bounds().unwrap_or_default()
+    }
+
+    fn actor(&self) -> Option<Point> {
+        Some(self.virus.position)
+    }
+
+    fn status(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("bursts", self.bursts.to_string()),
+            ("infections", self.infected.to_string()),
+        ]
+    }
+}
+
+/// A square map with odd side `size` (rounded up), about half infected.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size | 1;
+    let mut result = String::new();
+    for _ in 0..side {
+        for _ in 0..side {
+            result.push(if rng.chance(50) { '#' } else { '.' });
+        }
+        result.push('\n');
+    }
+    result
+}
+
+pub struct Day22;
+
+impl Puzzle for Day22 {
+    type Input = Grid<Status>;
+
+    fn day(&self) -> u8 {
+        22
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day22_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_infections(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let virus = Virus::new(start(input, ctx)?, Direction::Up);
+        let bursts = ctx.param("bursts1", 10000)?;
+        Ok(solve1(input.clone().into(), virus, bursts, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        let virus = Virus::new(start(input, ctx)?, Direction::Up);
+        let bursts = ctx.param("bursts2", 10_000_000)?;
+        Ok(solve2(input.clone().into(), virus, bursts, ctx)?.to_string())
+    }
+
+    fn animation(
+        &self,
+        input: &Self::Input,
+        part: u8,
+        ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        let bursts = match part {
+            1 => ctx.param("bursts1", 10000)?,
+            _ => ctx.param("bursts2", 10_000_000)?,
+        };
+        let animation = Animation::new(input, start(input, ctx)?, part, bursts);
+        Ok(Some(Box::new(animation)))
+    }
+
+    fn lint(&self, input: &str, ctx: &Context) -> Vec<Error> {
+        lint(input, ctx)
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("x", "column the virus starts at (default: the middle)"),
+            ("y", "row the virus starts at (default: the middle)"),
+            ("bursts1", "bursts in part 1 (default: 10000)"),
+            ("bursts2", "bursts in part 2 (default: 10000000)"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_animation() {
+        let infections =
+            parse_infections(crate::input::open("resources/day22_testdata.txt").unwrap()).unwrap();
+        let mut animation = Animation::new(&infections, center(&infections), 1, 10000);
+        for _ in 0..70 {
+            animation.step();
+        }
+        assert_eq!(
+            vec![
+                ("bursts", "70".to_string()),
+                ("infections", "41".to_string())
+            ],
+            animation.status()
+        );
+        while animation.step() {}
+        let virus = Virus::new(center(&infections), Direction::Up);
+        let expected = solve1(infections.clone().into(), virus, 10000, &Context::new()).unwrap();
+        assert_eq!(expected.to_string(), animation.status()[1].1);
+
+        let mut params = crate::context::Params::new();
+        params.insert("bursts1".to_string(), "7".to_string());
+        params.insert("x".to_string(), "0".to_string());
+        let ctx = Context::new().with_params(params);
+        let mut animation = Day22.animation(&infections, 1, &ctx).unwrap().unwrap();
+        while animation.step() {}
+        assert_eq!(
+            Day22.part1(&infections, &ctx).unwrap(),
+            animation.status()[1].1
+        );
+        assert_eq!(("bursts", "7".to_string()), animation.status()[0]);
+    }
+
+    #[test]
+    fn test_lint() {
+        let problems = |input: &str, ctx: &Context| -> Vec<String> {
+            lint(input, ctx).iter().map(|e| e.to_string()).collect()
+        };
+        let ctx = Context::new();
+        assert_eq!(
+            vec![
+                "<input>:2:2: expected `#` or `.` (found `x`)",
+                "<input>:3:4: expected a line of 3 characters (found `.`)",
+            ],
+            problems("..#\n#x.\n....", &ctx)
+        );
+        assert_eq!(
+            vec!["<input>:1:1: expected an odd side, for the map to have a middle (found `.#`)"],
+            problems(".#\n#.", &ctx)
+        );
+        assert_eq!(
+            vec!["<input>:1:1: expected a square map, not 1 lines of 3 characters (found `..#`)"],
+            problems("..#", &ctx)
+        );
+        let mut params = crate::context::Params::new();
+        params.insert("x".to_string(), "0".to_string());
+        params.insert("y".to_string(), "0".to_string());
+        assert!(problems("..#", &Context::new().with_params(params)).is_empty());
+    }
+}
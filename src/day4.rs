@@ -0,0 +1,110 @@
+use crate::context::Context;
+use crate::error::Result;
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    reader.lines().map(|line| Ok(line?)).collect()
+}
+
+pub fn solve1<T>(lines: T) -> usize
+where
+    T: IntoIterator<Item = String>,
+{
+    let mut result = 0;
+    for line in lines {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let old_len = words.len();
+        words.sort();
+        words.dedup();
+        if words.len() == old_len {
+            result += 1;
+        }
+    }
+    result
+}
+
+pub fn solve2<T>(lines: T) -> usize
+where
+    T: IntoIterator<Item = String>,
+{
+    let mut result = 0;
+    for line in lines {
+        let mut words: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
+        for word in words.iter_mut() {
+            let mut chars: Vec<char> = word.chars().collect();
+            chars.sort();
+            let sorted = chars.iter().collect::<String>();
+            *word = sorted.clone();
+        }
+        words.sort();
+        let old_len = words.len();
+        words.dedup();
+        if old_len == words.len() {
+            result += 1;
+        }
+    }
+    result
+}
+
+/// `size` passphrases of 2 to 10 short words, some of them repeated.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let mut words: Vec<String> = vec![];
+        for _ in 0..rng.range(2, 10) {
+            if !words.is_empty() && rng.chance(10) {
+                let word = rng.choose(&words).clone();
+                words.push(word);
+            } else {
+                let len = rng.range(1, 7) as usize;
+                words.push(rng.word(len));
+            }
+        }
+        result += &words.join(" ");
+        result += "\n";
+    }
+    result
+}
+
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day4_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        Ok(solve1(input.clone()).to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        Ok(solve2(input.clone()).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let lines = parse_input(crate::input::open("resources/day4_test.txt").unwrap()).unwrap();
+        assert_eq!(2, solve1(lines));
+    }
+}
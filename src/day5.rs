@@ -0,0 +1,124 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<isize>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
+            line.trim()
+                .parse::<isize>()
+                .map_err(|_| Error::token(i + 1, &line, line.trim(), "expected an offset"))
+        })
+        .collect()
+}
+
+pub fn solve1(nums: &[isize], ctx: &Context) -> Result<isize> {
+    let mut nums = nums.to_vec();
+    let mut pc = 0isize;
+    let mut result = 0;
+    loop {
+        if pc < 0 || pc >= nums.len() as isize {
+            break;
+        }
+        ctx.poll(result as u64, None)?;
+        result += 1;
+        let old_pc = pc;
+        pc += nums[pc as usize];
+        nums[old_pc as usize] += 1;
+    }
+    Ok(result)
+}
+
+pub fn solve2(nums: &[isize], ctx: &Context) -> Result<isize> {
+    let mut nums = nums.to_vec();
+    let mut pc = 0isize;
+    let mut result = 0;
+
+    loop {
+        if pc < 0 || pc >= nums.len() as isize {
+            break;
+        }
+        ctx.poll(result as u64, None)?;
+        result += 1;
+        let old_pc = pc;
+        pc += nums[pc as usize];
+
+        if nums[old_pc as usize] >= 3isize {
+            nums[old_pc as usize] -= 1;
+        } else {
+            nums[old_pc as usize] += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// `size` jump offsets, mostly backwards like the real input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| format!("{}\n", rng.range(-(size as i64), 2)))
+        .collect()
+}
+
+pub struct Day5;
+
+impl Puzzle for Day5 {
+    type Input = Vec<isize>;
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day5_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve1(input, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(input, ctx)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+            parse_input(crate::input::open("resources/day5_testdata.txt").unwrap()).unwrap();
+        assert_eq!(5, solve1(&input, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+            parse_input(crate::input::open("resources/day5_testdata.txt").unwrap()).unwrap();
+        assert_eq!(10, solve2(&input, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let ctx = Context::new().with_timeout(std::time::Duration::from_secs(0));
+        assert!(matches!(
+            solve1(&[-1_000_000_000], &ctx),
+            Err(Error::Cancelled)
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::context::Context;
+use crate::cycle::{self, Cycle};
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(mut reader: R) -> Result<Vec<usize>> {
+    let mut data = String::new();
+    reader.read_to_string(&mut data)?;
+    let mut result = vec![];
+    for (i, line) in data.lines().enumerate() {
+        for s in line.split_whitespace() {
+            let blocks = s
+                .parse::<usize>()
+                .map_err(|_| Error::token(i + 1, line, s, "expected a block count"))?;
+            result.push(blocks);
+        }
+    }
+    Ok(result)
+}
+
+fn find_target_bank(banks: &[usize]) -> Result<(usize, usize)> {
+    banks
+        .iter()
+        .enumerate()
+        .max_by(|(i, x), (j, y)| if x == y { j.cmp(i) } else { x.cmp(y) })
+        .ok_or_else(|| Error::solve("max not found"))
+        .map(|(i, b)| Ok((i, *b)))?
+}
+
+fn redistribute(banks: &[usize]) -> Result<Vec<usize>> {
+    let mut banks = banks.to_vec();
+    let (max_bank, blocks) = find_target_bank(&banks)?;
+    banks[max_bank] = 0;
+    for i in 1..=blocks {
+        let index = (i + max_bank) % banks.len();
+        banks[index] += 1;
+    }
+    Ok(banks)
+}
+
+fn solve(banks: &[usize], ctx: &Context) -> Result<Cycle> {
+    let mut cycles = 0;
+    cycle::find(banks.to_vec(), |banks| {
+        ctx.poll(cycles, None)?;
+        cycles += 1;
+        redistribute(banks)
+    })
+}
+
+/// Redistribution cycles until a configuration repeats.
+pub fn solve1(banks: &[usize], ctx: &Context) -> Result<usize> {
+    Ok(solve(banks, ctx)?.first_repeat())
+}
+
+/// Length of the loop of configurations.
+pub fn solve2(banks: &[usize], ctx: &Context) -> Result<usize> {
+    Ok(solve(banks, ctx)?.length)
+}
+
+/// `size` memory banks of up to 15 blocks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let banks: Vec<String> = (0..size.max(1))
+        .map(|_| rng.below(16).to_string())
+        .collect();
+    banks.join("\t") + "\n"
+}
+
+pub struct Day6;
+
+impl Puzzle for Day6 {
+    type Input = Vec<usize>;
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day6_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve1(input, ctx)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String> {
+        Ok(solve2(input, ctx)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let banks =
+            parse_input(crate::input::open("resources/day6_testdata.txt").unwrap()).unwrap();
+        assert_eq!(5, solve1(&banks, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let banks =
+            parse_input(crate::input::open("resources/day6_testdata.txt").unwrap()).unwrap();
+        assert_eq!(4, solve2(&banks, &Context::new()).unwrap());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::parser::{self, Cursor};
+use crate::puzzle::{parse_arg, unknown_view};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+pub type Op = (String, String, isize, String, String, isize);
+
+/// `reg inc|dec amount if reg op value`.
+pub fn parse_line(cursor: &mut Cursor) -> Result<Op> {
+    let register = cursor.identifier("a register")?;
+    let action = cursor.one_of(&["inc", "dec"], "`inc` or `dec`")?;
+    let amount = cursor.integer("an amount")?;
+    cursor.literal("if")?;
+    let other = cursor.identifier("a register")?;
+    let operator = cursor.one_of(&["==", "!=", "<", ">", "<=", ">="], "a comparison")?;
+    let value = cursor.integer("a number")?;
+    cursor.end("unexpected token")?;
+    Ok((
+        register.to_string(),
+        action.to_string(),
+        amount,
+        other.to_string(),
+        operator.to_string(),
+        value,
+    ))
+}
+
+fn eval_exp(a: isize, oper: &str, b: isize) -> Result<bool> {
+    let result = match oper {
+        "==" => a == b,
+        "!=" => a != b,
+        "<" => a < b,
+        ">" => a > b,
+        "<=" => a <= b,
+        ">=" => a >= b,
+        _ => return Err(Error::Solve(format!("unknown operator: {}", oper))),
+    };
+    Ok(result)
+}
+
+fn execute<T>(ops: T) -> Result<(HashMap<String, isize>, isize)>
+where
+    T: IntoIterator<Item = Op>,
+{
+    let mut regs = HashMap::<String, isize>::new();
+    let mut all_time_max = 0;
+    for op in ops {
+        let a = *regs.get(&op.3).unwrap_or(&0);
+        if eval_exp(a, &op.4, op.5)? {
+            if !regs.contains_key(&op.0) {
+                regs.insert(op.0.to_string(), 0);
+            }
+            let reg = regs
+                .get_mut(&op.0)
+                .ok_or_else(|| Error::solve("impossible None error"))?;
+            *reg = match op.1.as_str() {
+                "inc" => reg.checked_add(op.2),
+                "dec" => reg.checked_sub(op.2),
+                x => return Err(Error::Solve(format!("unsupported operator: {}", x))),
+            }
+            .ok_or_else(|| Error::Solve(format!("overflow in register {}", op.0)))?;
+            all_time_max = std::cmp::max(all_time_max, *reg);
+        }
+    }
+    Ok((regs, all_time_max))
+}
+
+pub fn solve<T>(ops: T) -> Result<(isize, isize)>
+where
+    T: IntoIterator<Item = Op>,
+{
+    let (regs, all_time_max) = execute(ops)?;
+    let part1 = *regs
+        .values()
+        .max()
+        .ok_or_else(|| Error::solve("max not found"))?;
+    Ok((part1, all_time_max))
+}
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<Op>> {
+    parser::lines(reader, parse_line)
+}
+
+/// `size` instructions on about `size / 4` registers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let registers: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            let len = rng.range(1, 3) as usize;
+            rng.word(len)
+        })
+        .collect();
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        result += &format!(
+            "{} {} {} if {} {} {}\n",
+            rng.choose(&registers),
+            rng.choose(&["inc", "dec"]),
+            rng.range(-1000, 1000),
+            rng.choose(&registers),
+            rng.choose(&["==", "!=", "<", ">", "<=", ">="]),
+            rng.range(-1000, 1000)
+        );
+    }
+    result
+}
+
+pub struct Day8;
+
+impl Puzzle for Day8 {
+    type Input = Vec<Op>;
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day8_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (part1, _) = solve(input.clone())?;
+        Ok(part1.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (_, part2) = solve(input.clone())?;
+        Ok(part2.to_string())
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        &["registers [LINE]"]
+    }
+
+    /// The registers after the first `LINE` instructions, all by default.
+    fn inspect(
+        &self,
+        input: &Self::Input,
+        view: &str,
+        args: &[&str],
+        _ctx: &Context,
+    ) -> Result<String> {
+        let lines = match (view, args) {
+            ("registers", []) => input.len(),
+            ("registers", [line]) => parse_arg("line", line)?,
+            _ => return Err(unknown_view(self.views(), view)),
+        };
+        let (regs, _) = execute(input.iter().take(lines).cloned())?;
+        let mut regs: Vec<(String, isize)> = regs.into_iter().collect();
+        regs.sort_unstable();
+        let lines: Vec<String> = regs
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_test() {
+        let input =
+            parse_input(crate::input::open("resources/day8_testdata.txt").unwrap()).unwrap();
+        let (part1, part2) = solve(input).unwrap();
+        assert_eq!(1, part1);
+        assert_eq!(10, part2);
+    }
+
+    #[test]
+    fn overflow_test() {
+        let input = "a inc 9223372036854775807 if b == 0\na inc 1 if b == 0\n";
+        let e = solve(parse_input(input.as_bytes()).unwrap()).unwrap_err();
+        assert_eq!("overflow in register a", e.to_string());
+        let input = "a dec 9223372036854775807 if b == 0\na dec 2 if b == 0\n";
+        let e = solve(parse_input(input.as_bytes()).unwrap()).unwrap_err();
+        assert_eq!("overflow in register a", e.to_string());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let e = parse_input("b inc 5 if a > 1\nc inc x if b < 5\n".as_bytes()).unwrap_err();
+        assert_eq!("<input>:2:7: expected an amount (found `x`)", e.to_string());
+        let e = parse_input("b inc 5 if a =< 1".as_bytes()).unwrap_err();
+        assert_eq!(
+            "<input>:1:14: expected a comparison (found `=<`)",
+            e.to_string()
+        );
+    }
+}
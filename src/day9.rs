@@ -0,0 +1,169 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use crate::Puzzle;
+use std::io::BufRead;
+
+pub fn parse_input<R: BufRead>(mut reader: R) -> Result<Vec<char>> {
+    let mut s = String::new();
+    reader.read_to_string(&mut s)?;
+    Ok(s.chars().collect())
+}
+
+/// Parse error at `index`, with line and column counted in `input`.
+fn error_at(input: &[char], index: usize, message: &str) -> Error {
+    let line = input[..index].iter().filter(|ch| **ch == '\n').count() + 1;
+    let line_start = input[..index]
+        .iter()
+        .rposition(|ch| *ch == '\n')
+        .map_or(0, |i| i + 1);
+    let token = input
+        .get(index)
+        .map(|ch| ch.to_string())
+        .unwrap_or_default();
+    Error::parse(line, index - line_start + 1, &token, message)
+}
+
+fn eat_garbage(input: &[char], index: usize) -> Result<(usize, usize)> {
+    let start = index;
+    let mut index = index + 1;
+    let mut carbage = 0;
+    loop {
+        match input.get(index) {
+            Some('>') => break,
+            Some('!') => index += 2,
+            Some(_) => {
+                index += 1;
+                carbage += 1;
+            }
+            None => return Err(error_at(input, start, "unterminated garbage")),
+        };
+    }
+    Ok((index, carbage))
+}
+
+pub fn solve(input: &[char]) -> Result<(usize, usize)> {
+    let mut index = 0;
+    let mut level = 0;
+    let mut result = 0;
+    let mut carbage = 0;
+
+    while index < input.len() {
+        let ch = input[index];
+        match ch {
+            '<' => {
+                let (i, carb) = eat_garbage(input, index)?;
+                index = i;
+                carbage += carb;
+            }
+            '{' => level += 1,
+            '}' => {
+                if level == 0 {
+                    return Err(error_at(input, index, "unmatched `}`"));
+                }
+                result += level;
+                level -= 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    Ok((result, carbage))
+}
+
+fn generate_garbage(rng: &mut Rng, result: &mut String) {
+    result.push('<');
+    for _ in 0..rng.below(10) {
+        match rng.below(10) {
+            0 => {
+                result.push('!');
+                result.push(*rng.choose(&['!', '>', '<', '{', '}', 'a']));
+            }
+            _ => result.push(*rng.choose(&['a', 'e', 'i', 'o', 'u', '{', '}', ',', '<', '\''])),
+        }
+    }
+    result.push('>');
+}
+
+fn generate_group(rng: &mut Rng, budget: &mut usize, result: &mut String) {
+    result.push('{');
+    let mut first = true;
+    while *budget > 0 && !rng.chance(20) {
+        *budget -= 1;
+        if !first {
+            result.push(',');
+        }
+        first = false;
+        if rng.chance(40) {
+            generate_garbage(rng, result);
+        } else {
+            generate_group(rng, budget, result);
+        }
+    }
+    result.push('}');
+}
+
+/// A stream of about `size` nested groups and garbage.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    let mut budget = size;
+    generate_group(rng, &mut budget, &mut result);
+    result + "\n"
+}
+
+pub struct Day9;
+
+impl Puzzle for Day9 {
+    type Input = Vec<char>;
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn input_path(&self) -> &'static str {
+        "resources/day9_input.txt"
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (part1, _) = solve(input)?;
+        Ok(part1.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Result<String> {
+        let (_, part2) = solve(input)?;
+        Ok(part2.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input: Vec<char> = "{{<ab>},{<!!>}}".chars().collect();
+        assert_eq!((5, 2), solve(&input).unwrap());
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let input: Vec<char> = "{}\n{<ab!>".chars().collect();
+        let e = solve(&input).unwrap_err();
+        assert_eq!(
+            "<input>:2:2: unterminated garbage (found `<`)",
+            e.to_string()
+        );
+        let input: Vec<char> = "{}}".chars().collect();
+        let e = solve(&input).unwrap_err();
+        assert_eq!("<input>:1:3: unmatched `}` (found `}`)", e.to_string());
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::io;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error shared by every day's parser and solver.
+///
+/// Parse errors point at the offending token: `line` and `column` are
+/// 1-based, columns count characters. The input path is not known to the
+/// parsers themselves and is attached by whoever opened the input, see
+/// `Error::with_path`.
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: Option<String>,
+        source: io::Error,
+    },
+    Parse {
+        path: Option<String>,
+        line: usize,
+        column: usize,
+        token: String,
+        message: String,
+    },
+    Solve(String),
+    /// The solver was stopped through its `Context`.
+    Cancelled,
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, token: &str, message: &str) -> Error {
+        Error::Parse {
+            path: None,
+            line,
+            column,
+            token: token.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Parse error for `token`, which must be a slice of `line`.
+    pub fn token(line_no: usize, line: &str, token: &str, message: &str) -> Error {
+        Error::parse(line_no, column(line, token), token, message)
+    }
+
+    pub fn solve(message: &str) -> Error {
+        Error::Solve(message.to_string())
+    }
+
+    /// Moves a parse error to `line_no`, for errors from parsing a line on
+    /// its own.
+    pub fn on_line(mut self, line_no: usize) -> Error {
+        if let Error::Parse { line, .. } = &mut self {
+            *line = line_no;
+        }
+        self
+    }
+
+    /// Sets the input path if the error does not have one yet.
+    pub fn with_path(mut self, input: &str) -> Error {
+        match &mut self {
+            Error::Io { path, .. } | Error::Parse { path, .. } if path.is_none() => {
+                *path = Some(input.to_string());
+            }
+            _ => {}
+        }
+        self
+    }
+}
+
+/// 1-based character column of `token` within `line`. `token` must be a
+/// slice of `line`, as returned by `split` and friends; otherwise the column
+/// just past the end of `line` is returned.
+pub fn column(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= line.len() && line.is_char_boundary(*offset))
+        .unwrap_or(line.len());
+    line[..offset].chars().count() + 1
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => match path {
+                Some(path) => write!(f, "{}: {}", path, source),
+                None => write!(f, "{}", source),
+            },
+            Error::Parse {
+                path,
+                line,
+                column,
+                token,
+                message,
+            } => {
+                let path = path.as_deref().unwrap_or("<input>");
+                write!(f, "{}:{}:{}: {}", path, line, column, message)?;
+                if token.is_empty() {
+                    write!(f, " (found nothing)")
+                } else {
+                    write!(f, " (found `{}`)", token.escape_debug())
+                }
+            }
+            Error::Solve(message) => write!(f, "{}", message),
+            Error::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl Clone for Error {
+    fn clone(&self) -> Error {
+        match self {
+            Error::Io { path, source } => Error::Io {
+                path: path.clone(),
+                source: io::Error::new(source.kind(), source.to_string()),
+            },
+            Error::Parse {
+                path,
+                line,
+                column,
+                token,
+                message,
+            } => Error::Parse {
+                path: path.clone(),
+                line: *line,
+                column: *column,
+                token: token.clone(),
+                message: message.clone(),
+            },
+            Error::Solve(message) => Error::Solve(message.clone()),
+            Error::Cancelled => Error::Cancelled,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Error {
+        Error::Io { path: None, source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "äb: 12, x";
+        let tokens: Vec<&str> = line.split(", ").collect();
+        assert_eq!(1, column(line, tokens[0]));
+        assert_eq!(9, column(line, tokens[1]));
+        assert_eq!(10, column(line, "not a slice"));
+    }
+
+    #[test]
+    fn test_display() {
+        let e = Error::token(3, "0: x", &"0: x"[3..], "invalid range").with_path("in.txt");
+        assert_eq!("in.txt:3:4: invalid range (found `x`)", e.to_string());
+        let e = Error::parse(1, 5, "", "missing range")
+            .with_path("a")
+            .with_path("b");
+        assert_eq!("a:1:5: missing range (found nothing)", e.to_string());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::check::{self, Outcome};
+use crate::config::Config;
+use crate::context::{Context, Params};
+use crate::error::{Error, Result};
+use crate::input::{self, Source};
+use crate::runner;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// Directory searched for examples by default.
+pub const EXAMPLES_DIR: &str = "resources";
+
+/// An example input, `dayN_test*` in the examples directory, with the
+/// answers and the parameters of the day listed in its sidecar: the same
+/// name with an `.expected` extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub day: u8,
+    pub path: PathBuf,
+    pub sidecar: PathBuf,
+}
+
+/// Result of running one example.
+#[derive(Debug, Clone)]
+pub enum Run {
+    /// Outcome of every part listed in the sidecar.
+    Parts(Vec<(u8, Outcome)>),
+    NotImplemented,
+    /// The sidecar is missing or invalid.
+    BadSidecar(Error),
+}
+
+impl Run {
+    pub fn is_ok(&self) -> bool {
+        match self {
+            Run::Parts(parts) => parts.iter().all(|(_, outcome)| outcome.is_ok()),
+            Run::NotImplemented => true,
+            Run::BadSidecar(_) => false,
+        }
+    }
+}
+
+/// Day of an example file name such as `day12_testdata2.txt`.
+fn example_day(file_name: &str) -> Option<u8> {
+    let rest = file_name.strip_prefix("day")?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+    if !rest[digits..].starts_with("_test") || file_name.ends_with(".expected") {
+        return None;
+    }
+    rest[..digits].parse().ok()
+}
+
+/// Every example in `dir`, ordered by day and file name.
+pub fn discover(dir: &Path) -> Result<Vec<Example>> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| Error::from(e).with_path(&dir.display().to_string()))?;
+    let mut result = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let day = match path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(example_day)
+        {
+            Some(day) => day,
+            None => continue,
+        };
+        result.push(Example {
+            day,
+            sidecar: path.with_extension("expected"),
+            path,
+        });
+    }
+    result.sort_by(|a, b| (a.day, &a.path).cmp(&(b.day, &b.path)));
+    Ok(result)
+}
+
+/// Contents of a sidecar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expected {
+    /// Parameters the example is solved with, such as the 5 programs of
+    /// day 16.
+    pub params: Params,
+    pub answers: Vec<(u8, String)>,
+}
+
+/// Parses a sidecar: one `part N: answer` or `name = value` per line.
+pub fn parse_expected<R: BufRead>(reader: R) -> Result<Expected> {
+    let mut result = Expected::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with("part ") {
+            if let Some((name, value)) = line.split_once('=') {
+                let (name, value) = (name.trim(), value.trim());
+                result.params.insert(name.to_string(), value.to_string());
+                continue;
+            }
+        }
+        let (part, answer) = match line.strip_prefix("part ").and_then(|l| l.split_once(": ")) {
+            Some(split) => split,
+            None => {
+                return Err(Error::token(
+                    i + 1,
+                    &line,
+                    &line,
+                    "expected `part N: answer` or `name = value`",
+                ))
+            }
+        };
+        match part {
+            "1" | "2" => result
+                .answers
+                .push((part.parse().unwrap(), answer.trim().to_string())),
+            _ => return Err(Error::token(i + 1, &line, part, "invalid part")),
+        }
+    }
+    Ok(result)
+}
+
+impl Example {
+    /// Solves the parts listed in the sidecar and compares the answers.
+    pub fn run(&self) -> Run {
+        let day = match crate::day(self.day) {
+            Some(day) => day,
+            None => return Run::NotImplemented,
+        };
+        let sidecar = self.sidecar.display().to_string();
+        let expected = match input::open(&sidecar).and_then(parse_expected) {
+            Ok(expected) => expected,
+            Err(e) => return Run::BadSidecar(e.with_path(&sidecar)),
+        };
+        let mut config = Config::new();
+        for (name, value) in &expected.params {
+            if let Err(e) = config.set(self.day, name, value) {
+                return Run::BadSidecar(e);
+            }
+        }
+        let ctx = Context::new().with_params(config.params(self.day));
+        let parts: Vec<u8> = expected.answers.iter().map(|(part, _)| *part).collect();
+        let source = Source::Path(self.path.display().to_string());
+        let outcomes = runner::run(day, &source, &parts, &ctx)
+            .iter()
+            .zip(&expected.answers)
+            .map(|(answer, (part, expected))| (*part, check::compare(answer, Some(expected))))
+            .collect();
+        Run::Parts(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_day() {
+        assert_eq!(Some(12), example_day("day12_testdata2.txt"));
+        assert_eq!(Some(4), example_day("day4_test.txt"));
+        assert_eq!(None, example_day("day4_test.expected"));
+        assert_eq!(None, example_day("day12_input.txt"));
+        assert_eq!(None, example_day("answers.txt"));
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            vec![(1, "6".to_string()), (2, "ABC".to_string())],
+            parse_expected("part 1: 6\n\npart 2: ABC\n".as_bytes())
+                .unwrap()
+                .answers
+        );
+        let expected = parse_expected("programs = abcde\npart 1: a=b\n".as_bytes()).unwrap();
+        assert_eq!(Some("abcde"), expected.params.get("programs").map(|v| &**v));
+        assert_eq!(vec![(1, "a=b".to_string())], expected.answers);
+        let e = parse_expected("part 3: 6".as_bytes()).unwrap_err();
+        assert_eq!("<input>:1:6: invalid part (found `3`)", e.to_string());
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = discover(Path::new(EXAMPLES_DIR)).unwrap();
+        assert!(!examples.is_empty());
+        for example in examples {
+            let run = example.run();
+            assert!(run.is_ok(), "{}: {:?}", example.path.display(), run);
+        }
+    }
+}
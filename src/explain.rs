@@ -0,0 +1,141 @@
+use crate::report::json_string;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Value of a field of an `Event`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! number_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Value {
+                Value::Number(n as i64)
+            }
+        })*
+    };
+}
+
+number_from!(u8, u32, u64, usize, i32, i64, isize);
+
+impl From<char> for Value {
+    fn from(ch: char) -> Value {
+        Value::Text(ch.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::Text(s)
+    }
+}
+
+/// One step of the derivation of an answer, such as a matching digit pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    pub fn new(name: &'static str) -> Event {
+        Event {
+            name,
+            fields: vec![],
+        }
+    }
+
+    pub fn with(mut self, field: &'static str, value: impl Into<Value>) -> Event {
+        self.fields.push((field, value.into()));
+        self
+    }
+
+    /// `name field=value ...` on one line.
+    pub fn text(&self) -> String {
+        let mut result = self.name.to_string();
+        for (field, value) in &self.fields {
+            result += &format!(" {}={}", field, value);
+        }
+        result
+    }
+
+    /// A JSON object with the name under `event`, after the fields of
+    /// `prefix`.
+    pub fn json(&self, prefix: &[(&str, Value)]) -> String {
+        let fields: Vec<String> = prefix
+            .iter()
+            .map(|(field, value)| (*field, value))
+            .chain(std::iter::once(("event", &Value::from(self.name))))
+            .chain(self.fields.iter().map(|(field, value)| (*field, value)))
+            .map(|(field, value)| {
+                let value = match value {
+                    Value::Number(n) => n.to_string(),
+                    Value::Text(s) => json_string(s),
+                };
+                format!("{}:{}", json_string(field), value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// Collects the events solvers explain their answers with, from any thread.
+#[derive(Debug, Clone, Default)]
+pub struct Explain(Arc<Mutex<Vec<Event>>>);
+
+impl Explain {
+    pub fn new() -> Explain {
+        Explain::default()
+    }
+
+    pub fn push(&self, event: Event) {
+        self.0.lock().unwrap().push(event);
+    }
+
+    /// The events so far, leaving none behind.
+    pub fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let event = Event::new("letter").with("x", 5).with("letter", 'A');
+        assert_eq!("letter x=5 letter=A", event.text());
+        assert_eq!(
+            r#"{"day":19,"event":"letter","x":5,"letter":"A"}"#,
+            event.json(&[("day", Value::from(19))])
+        );
+    }
+
+    #[test]
+    fn test_take() {
+        let explain = Explain::new();
+        explain.push(Event::new("a"));
+        explain.clone().push(Event::new("b"));
+        assert_eq!(2, explain.take().len());
+        assert!(explain.take().is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::error::{Error, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+/// Where the puzzle inputs are downloaded from by default.
+pub const BASE_URL: &str = "https://adventofcode.com/2017";
+
+/// Environment variables overriding the session token and base URL.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = concat!("aoc_2017/", env!("CARGO_PKG_VERSION"), " (input fetcher)");
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fetched {
+    /// The input was already on disk and was left alone.
+    Cached,
+    /// The input was downloaded, this many bytes.
+    Downloaded(usize),
+}
+
+/// True if `path` holds an input, empty files do not count.
+pub fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Downloads the input of `day` from `base_url` into `path`, unless it is
+/// cached there already. `session` is the value of the site's session
+/// cookie and is only needed for downloading.
+pub fn fetch(day: u8, base_url: &str, session: Option<&str>, path: &Path) -> Result<Fetched> {
+    if is_cached(path) {
+        return Ok(Fetched::Cached);
+    }
+    let session = session.ok_or_else(|| {
+        Error::Solve(format!(
+            "no session token, set {} or pass --session",
+            SESSION_VAR
+        ))
+    })?;
+    let url = format!("{}/day/{}/input", base_url.trim_end_matches('/'), day);
+    let data = download(&url, session).map_err(|e| Error::from(e).with_path(&url))?;
+
+    // Write next to the target and rename, so that an interrupted download
+    // never leaves a partial input that would count as cached.
+    let write = || -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("partial");
+        fs::write(&partial, &data)?;
+        fs::rename(&partial, path)
+    };
+    write().map_err(|e| Error::from(e).with_path(&path.display().to_string()))?;
+    Ok(Fetched::Downloaded(data.len()))
+}
+
+fn download(url: &str, session: &str) -> io::Result<Vec<u8>> {
+    let response = ureq::get(url)
+        .timeout(Duration::from_secs(30))
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                io::Error::other(format!("HTTP {} {}", code, response.status_text()))
+            }
+            ureq::Error::Transport(e) => io::Error::other(e.to_string()),
+        })?;
+    let mut data = vec![];
+    response.into_reader().read_to_end(&mut data)?;
+    if data.is_empty() {
+        return Err(io::Error::other("empty input"));
+    }
+    Ok(data)
+}
@@ -0,0 +1,187 @@
+use crate::context::Context;
+use crate::puzzle::Solver;
+use crate::rng::Rng;
+use crate::runner::panic_message;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Time after which a single case counts as hanging.
+pub const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Days whose solvers are fuzzed too, not just their parsers. The other
+/// solvers may legitimately run for a very long time on random input.
+pub const SOLVE_DAYS: &[u8] = &[9];
+
+/// How a case broke the "returns Ok or Err" contract.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    Panic(String),
+    Hang,
+}
+
+/// An input that made a day panic or hang.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub day: u8,
+    pub input: Vec<u8>,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let input = String::from_utf8_lossy(&self.input);
+        match &self.problem {
+            Problem::Panic(message) => write!(f, "day {}: panic: {}", self.day, message)?,
+            Problem::Hang => write!(f, "day {}: no result after {:?}", self.day, TIMEOUT)?,
+        }
+        write!(f, "\ninput: {:?}", input)
+    }
+}
+
+/// Parses `input` with `day`, and solves it if `day` is in `SOLVE_DAYS`.
+/// Errors are fine, panics and hangs are not.
+pub fn run_case(day: &'static dyn Solver, input: &[u8]) -> Option<Problem> {
+    let input = input.to_vec();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            if let Ok(parsed) = day.parse(&mut input.as_slice()) {
+                if SOLVE_DAYS.contains(&day.day()) {
+                    let ctx = Context::new().with_timeout(TIMEOUT);
+                    let _ = day.solve(&parsed, 1, &ctx);
+                    let _ = day.solve(&parsed, 2, &ctx);
+                }
+            }
+        }));
+        let _ = sender.send(result.map_err(panic_message));
+    });
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(Ok(())) => None,
+        Ok(Err(message)) => Some(Problem::Panic(message)),
+        Err(_) => Some(Problem::Hang),
+    }
+}
+
+const INTERESTING: &[&[u8]] = &[
+    b"0",
+    b"1",
+    b"-1",
+    b"-",
+    b"99999999999999999999",
+    b"2147483647",
+    b" ",
+    b",",
+    b"\n",
+    b"\r\n",
+    b":",
+    b"/",
+    b"<",
+    b">",
+    b"!",
+    b"{",
+    b"}",
+    b"|",
+    b"+",
+    b"<->",
+    b"\xff",
+    b"\xc3\xa4",
+];
+
+/// Random bytes, or a generated input with a few random edits.
+pub fn mutate(day: &dyn Solver, rng: &mut Rng) -> Vec<u8> {
+    if rng.chance(10) {
+        return (0..rng.below(64)).map(|_| rng.below(256) as u8).collect();
+    }
+    let size = rng.below(20);
+    let mut input = day.generate(rng, size).into_bytes();
+    for _ in 0..rng.range(1, 4) {
+        let at = rng.below(input.len() + 1);
+        let len = rng.below(input.len() - at + 1).min(8);
+        match rng.below(4) {
+            0 => {
+                input.drain(at..at + len);
+            }
+            1 => input.truncate(at),
+            2 => {
+                let chunk = input[at..at + len].to_vec();
+                input.splice(at..at, chunk);
+            }
+            _ => {
+                let token = rng.choose(INTERESTING);
+                input.splice(at..at + len.min(1), token.iter().copied());
+            }
+        }
+    }
+    input
+}
+
+/// Shortens an input that panics while it keeps panicking, returning the
+/// shortest input found and its panic.
+pub fn shrink(
+    day: &'static dyn Solver,
+    mut input: Vec<u8>,
+    problem: Problem,
+) -> (Vec<u8>, Problem) {
+    let mut problem = problem;
+    if problem == Problem::Hang {
+        return (input, problem);
+    }
+    let mut chunk = input.len() / 2;
+    while chunk > 0 {
+        let mut at = 0;
+        while at + chunk <= input.len() {
+            let mut candidate = input.clone();
+            candidate.drain(at..at + chunk);
+            match run_case(day, &candidate) {
+                Some(panic @ Problem::Panic(_)) => {
+                    input = candidate;
+                    problem = panic;
+                }
+                _ => at += chunk,
+            }
+        }
+        chunk /= 2;
+    }
+    (input, problem)
+}
+
+/// Runs `cases` random inputs through `day`, returning the first failure,
+/// shrunk.
+pub fn fuzz(day: &'static dyn Solver, rng: &mut Rng, cases: usize) -> Option<Failure> {
+    for _ in 0..cases {
+        let input = mutate(day, rng);
+        if let Some(problem) = run_case(day, &input) {
+            let (input, problem) = shrink(day, input, problem);
+            return Some(Failure {
+                day: day.day(),
+                input,
+                problem,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_case() {
+        let day9 = crate::day(9).unwrap();
+        assert_eq!(None, run_case(day9, b"{<a>,{}}"));
+        assert_eq!(None, run_case(day9, b"{<a"));
+    }
+
+    #[test]
+    fn test_fuzz() {
+        for day in crate::days() {
+            if let Some(failure) = fuzz(*day, &mut Rng::new(u64::from(day.day())), 300) {
+                panic!("{}", failure);
+            }
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Directed graph stored as adjacency lists. Undirected graphs add every
+/// edge in both directions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph<N: Eq + Hash> {
+    edges: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash> Default for Graph<N> {
+    fn default() -> Graph<N> {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    pub fn add_node(&mut self, node: N) {
+        self.edges.entry(node).or_default();
+    }
+
+    /// Adds an edge from `from` to `to`, and both nodes if needed.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_node(to.clone());
+        self.edges.entry(from).or_default().push(to);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N) {
+        self.add_edge(a.clone(), b.clone());
+        self.add_edge(b, a);
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.edges.contains_key(node)
+    }
+
+    /// The nodes `node` has an edge to, empty for unknown nodes.
+    pub fn neighbours(&self, node: &N) -> &[N] {
+        self.edges.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every node, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn bfs(&self, start: N) -> Bfs<N, impl FnMut(&N) -> Vec<N> + '_> {
+        bfs(start, move |node| self.neighbours(node).to_vec())
+    }
+
+    pub fn dfs(&self, start: N) -> Dfs<N, impl FnMut(&N) -> Vec<N> + '_> {
+        dfs(start, move |node| self.neighbours(node).to_vec())
+    }
+
+    /// Fewest edges from `from` to `to`, both included.
+    pub fn shortest_path(&self, from: N, to: &N) -> Option<Vec<N>> {
+        shortest_path(
+            from,
+            |node| node == to,
+            |node| self.neighbours(node).to_vec(),
+        )
+    }
+
+    /// Groups of nodes connected by edges in either direction.
+    pub fn components(&self) -> Vec<Vec<N>> {
+        let nodes: Vec<&N> = self.edges.keys().collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut sets = UnionFind::new(nodes.len());
+        for (from, to) in self.edges.iter() {
+            for to in to {
+                sets.union(index[from], index[to]);
+            }
+        }
+        let mut groups: HashMap<usize, Vec<N>> = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            groups
+                .entry(sets.find(i))
+                .or_default()
+                .push((*node).clone());
+        }
+        groups.into_values().collect()
+    }
+}
+
+/// Disjoint sets of the elements `0..n`.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// `n` sets of one element each.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            count: n,
+        }
+    }
+
+    /// Representative of the set holding `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut x = x;
+        while self.parents[x] != root {
+            x = std::mem::replace(&mut self.parents[x], root);
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, returning false if they already
+    /// were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set holding `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Breadth first traversal, yielding each reachable node once with its
+/// distance from the start.
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    seen: HashSet<N>,
+    neighbours: F,
+}
+
+/// Visits the nodes reachable from `start`, `neighbours` giving the nodes
+/// one edge away from a node.
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs {
+        queue: VecDeque::from(vec![(start.clone(), 0)]),
+        seen: HashSet::from([start]),
+        neighbours,
+    }
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<(N, usize)> {
+        let (node, distance) = self.queue.pop_front()?;
+        for next in (self.neighbours)(&node) {
+            if self.seen.insert(next.clone()) {
+                self.queue.push_back((next, distance + 1));
+            }
+        }
+        Some((node, distance))
+    }
+}
+
+/// Depth first traversal, yielding each reachable node once.
+pub struct Dfs<N, F> {
+    stack: Vec<N>,
+    seen: HashSet<N>,
+    neighbours: F,
+}
+
+/// Visits the nodes reachable from `start` depth first, see `bfs`.
+pub fn dfs<N, F, I>(start: N, neighbours: F) -> Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Dfs {
+        stack: vec![start],
+        seen: HashSet::new(),
+        neighbours,
+    }
+}
+
+impl<N, F, I> Iterator for Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        while let Some(node) = self.stack.pop() {
+            if !self.seen.insert(node.clone()) {
+                continue;
+            }
+            let mut next: Vec<N> = (self.neighbours)(&node)
+                .into_iter()
+                .filter(|n| !self.seen.contains(n))
+                .collect();
+            // Visit the neighbours in the order they were given.
+            next.reverse();
+            self.stack.extend(next);
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// Groups of `nodes` connected through `neighbours`, which must be
+/// symmetric.
+pub fn components<N, F, I>(nodes: impl IntoIterator<Item = N>, mut neighbours: F) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut result = vec![];
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component: Vec<N> = bfs(node, &mut neighbours).map(|(n, _)| n).collect();
+        seen.extend(component.iter().cloned());
+        result.push(component);
+    }
+    result
+}
+
+/// Fewest steps from `start` to a node for which `goal` holds, as the
+/// list of nodes on the way, both ends included.
+pub fn shortest_path<N, G, F, I>(start: N, mut goal: G, mut neighbours: F) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut queue = VecDeque::from(vec![start.clone()]);
+    let mut seen = HashSet::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            let mut path = vec![node];
+            while let Some(parent) = parents.get(&path[path.len() - 1]) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 - 1 - 2 - 3, 1 - 3 and 4 - 5.
+    fn graph() -> Graph<u32> {
+        let mut graph = Graph::new();
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (1, 3), (4, 5)] {
+            graph.add_undirected_edge(a, b);
+        }
+        graph.add_node(6);
+        graph
+    }
+
+    #[test]
+    fn test_traversal() {
+        let graph = graph();
+        let distances: Vec<(u32, usize)> = graph.bfs(0).collect();
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 2)], distances);
+        assert_eq!(vec![0, 1, 2, 3], graph.dfs(0).collect::<Vec<_>>());
+        assert_eq!(Some(vec![0, 1, 3]), graph.shortest_path(0, &3));
+        assert_eq!(None, graph.shortest_path(0, &4));
+    }
+
+    #[test]
+    fn test_components() {
+        let mut groups = graph().components();
+        groups.iter_mut().for_each(|g| g.sort_unstable());
+        groups.sort();
+        assert_eq!(vec![vec![0, 1, 2, 3], vec![4, 5], vec![6]], groups);
+
+        let groups = components(0..10, |n: &u32| vec![n ^ 1]);
+        assert_eq!(5, groups.len());
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert!(sets.union(1, 4));
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(0, 2));
+        assert_eq!(4, sets.size(3));
+        assert_eq!(2, sets.count());
+    }
+}
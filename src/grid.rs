@@ -0,0 +1,430 @@
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+/// A position on a grid, `y` growing downwards as in the puzzle maps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const fn new(x: isize, y: isize) -> Point {
+        Point { x, y }
+    }
+
+    /// The neighbouring point in `direction`.
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+
+    /// The four points sharing an edge with this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.iter().map(move |d| self.step(*d))
+    }
+
+    /// The eight points sharing an edge or a corner with this one.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Point::new(dx, dy)))
+            .filter(|offset| *offset != Point::default())
+            .map(move |offset| self + offset)
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: isize) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.turn_left().turn_left()
+    }
+
+    /// The step taken when moving one cell in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+/// Grid of unbounded size, only storing the cells that were set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Reads a character map, `f` returning the value of each cell or
+    /// `None` to leave it empty.
+    pub fn parse<R, F>(reader: R, mut f: F) -> Result<SparseGrid<T>>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut grid = SparseGrid::new();
+        for (y, line) in reader.lines().enumerate() {
+            for (x, ch) in line?.chars().enumerate() {
+                if let Some(value) = f(ch) {
+                    grid.insert(Point::new(x as isize, y as isize), value);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Sets `point`, returning its previous value.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    /// Smallest and largest corner of the rectangle holding every set
+    /// cell, `None` if there are none.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> SparseGrid<T> {
+        let width = grid.width;
+        let cells = grid
+            .cells
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                (
+                    Point::new((i % width) as isize, (i / width) as isize),
+                    value,
+                )
+            })
+            .collect();
+        SparseGrid { cells }
+    }
+}
+
+/// Rectangular grid with its top left corner at (0, 0), every cell set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads a character map whose lines all have the same length. `f`
+    /// returns the value of each cell, or `None` for characters that are
+    /// reported as errors with `message`.
+    pub fn parse<R, F>(reader: R, message: &str, mut f: F) -> Result<Grid<T>>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in reader.lines().enumerate() {
+            let line = line?;
+            for (i, ch) in line.char_indices() {
+                match f(ch) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        let token = &line[i..i + ch.len_utf8()];
+                        return Err(Error::token(y + 1, &line, token, message));
+                    }
+                }
+            }
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    let column = width.min(len) + 1;
+                    let token: String = line.chars().skip(width).collect();
+                    let message = format!("expected a line of {} characters", width);
+                    return Err(Error::parse(y + 1, column, &token, &message));
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as isize).contains(&point.x) && (0..self.height as isize).contains(&point.y)
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, value)| {
+            (
+                Point::new((i % width) as isize, (i / width) as isize),
+                value,
+            )
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(value) => value,
+            None => panic!("{:?} is outside of the grid", point),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside of the grid", point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let p = Point::new(2, 3);
+        assert_eq!(Point::new(3, 1), p + Point::new(1, -2));
+        assert_eq!(Point::new(-4, -6), -p * 2);
+        assert_eq!(Point::new(2, 2), p.step(Direction::Up));
+        assert_eq!(5, p.manhattan(Point::default()));
+        assert_eq!(4, p.neighbours4().count());
+        let neighbours: Vec<Point> = p.neighbours8().collect();
+        assert_eq!(8, neighbours.len());
+        assert!(neighbours.iter().all(|n| n.manhattan(p) <= 2 && *n != p));
+    }
+
+    #[test]
+    fn test_direction() {
+        for d in Direction::ALL.iter() {
+            assert_eq!(*d, d.turn_left().turn_right());
+            assert_eq!(-d.offset(), d.reverse().offset());
+        }
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Left, Direction::Right.reverse());
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(&b"#.\n.#\n"[..], "expected `#` or `.`", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert!(grid[Point::new(1, 1)]);
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+
+        let e = Grid::parse(&b"#.\n.x\n"[..], "expected `#` or `.`", |ch| match ch {
+            '#' | '.' => Some(ch),
+            _ => None,
+        })
+        .unwrap_err();
+        assert_eq!(
+            "<input>:2:2: expected `#` or `.` (found `x`)",
+            e.to_string()
+        );
+        let e = Grid::parse(&b"##\n###\n"[..], "", Some).unwrap_err();
+        assert_eq!(
+            "<input>:2:3: expected a line of 2 characters (found `#`)",
+            e.to_string()
+        );
+
+        let sparse =
+            SparseGrid::parse(&b" a\nb \n"[..], |ch| Some(ch).filter(|ch| *ch != ' ')).unwrap();
+        assert_eq!(2, sparse.len());
+        assert_eq!(Some(&'b'), sparse.get(Point::new(0, 1)));
+        assert_eq!(Some((Point::new(0, 0), Point::new(1, 1))), sparse.bounds());
+        assert_eq!(4, SparseGrid::from(grid).len());
+    }
+}
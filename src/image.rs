@@ -0,0 +1,236 @@
+use crate::animate::{Colour, Simulation};
+use crate::grid::Point;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+
+    /// Perceived brightness, 0 to 255.
+    pub fn luma(self) -> u8 {
+        let Rgb(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+}
+
+impl From<Colour> for Rgb {
+    fn from(colour: Colour) -> Rgb {
+        match colour {
+            Colour::Red => Rgb(220, 40, 40),
+            Colour::Green => Rgb(40, 170, 60),
+            Colour::Yellow => Rgb(230, 190, 30),
+            Colour::Blue => Rgb(50, 90, 220),
+            Colour::Magenta => Rgb(190, 50, 190),
+            Colour::Cyan => Rgb(40, 180, 200),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+}
+
+/// The cells of `sim` within its bounds, one pixel each. Coloured cells
+/// keep their colour, blanks are white, `.` light grey and anything else
+/// black.
+pub fn snapshot(sim: &dyn Simulation) -> Image {
+    let (min, max) = sim.bounds();
+    let size = max - min + Point::new(1, 1);
+    let (width, height) = (size.x.max(0) as usize, size.y.max(0) as usize);
+    let mut image = Image::new(width, height, Rgb::WHITE);
+    for y in 0..height {
+        for x in 0..width {
+            let cell = sim.cell(min + Point::new(x as isize, y as isize));
+            let colour = match (cell.colour, cell.ch) {
+                (Some(colour), _) => colour.into(),
+                (None, ' ') => Rgb::WHITE,
+                (None, '.') => Rgb(220, 220, 220),
+                (None, _) => Rgb::BLACK,
+            };
+            image.set(x, y, colour);
+        }
+    }
+    image
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Black and white netpbm bitmap.
+    Pbm,
+    /// Grey netpbm graymap.
+    Pgm,
+    /// Colour netpbm pixmap.
+    Ppm,
+    Svg,
+}
+
+impl Format {
+    /// The format matching the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "pbm" => Some(Format::Pbm),
+            "pgm" => Some(Format::Pgm),
+            "ppm" => Some(Format::Ppm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `image` in the binary variant of the netpbm formats, or as SVG
+/// with `scale` units per pixel.
+pub fn write(image: &Image, format: Format, scale: usize, out: &mut dyn Write) -> io::Result<()> {
+    let (width, height) = (image.width, image.height);
+    match format {
+        Format::Pbm => {
+            write!(out, "P4\n{} {}\n", width, height)?;
+            for row in image.rows() {
+                // Eight pixels per byte, 1 is black, rows padded to a byte.
+                let mut bytes = vec![0u8; width.div_ceil(8)];
+                for (x, pixel) in row.iter().enumerate() {
+                    if pixel.luma() < 128 {
+                        bytes[x / 8] |= 0x80 >> (x % 8);
+                    }
+                }
+                out.write_all(&bytes)?;
+            }
+        }
+        Format::Pgm => {
+            write!(out, "P5\n{} {}\n255\n", width, height)?;
+            let bytes: Vec<u8> = image.pixels.iter().map(|p| p.luma()).collect();
+            out.write_all(&bytes)?;
+        }
+        Format::Ppm => {
+            write!(out, "P6\n{} {}\n255\n", width, height)?;
+            let bytes: Vec<u8> = image
+                .pixels
+                .iter()
+                .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+                .collect();
+            out.write_all(&bytes)?;
+        }
+        Format::Svg => write_svg(image, scale, out)?,
+    }
+    out.flush()
+}
+
+/// One rectangle per run of equal pixels in a row.
+fn write_svg(image: &Image, scale: usize, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+        image.width * scale,
+        image.height * scale,
+        image.width,
+        image.height
+    )?;
+    for (y, row) in image.rows().enumerate() {
+        let mut x = 0;
+        for run in row.chunk_by(|a, b| a == b) {
+            let Rgb(r, g, b) = run[0];
+            if run[0] != Rgb::WHITE {
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" \
+                     fill=\"#{:02x}{:02x}{:02x}\"/>",
+                    x,
+                    y,
+                    run.len(),
+                    r,
+                    g,
+                    b
+                )?;
+            }
+            x += run.len();
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let mut image = Image::new(9, 2, Rgb::WHITE);
+        image.set(0, 0, Rgb::BLACK);
+        image.set(8, 0, Rgb::BLACK);
+        image.set(1, 1, Rgb(255, 0, 0));
+        image.set(2, 1, Rgb(255, 0, 0));
+        image
+    }
+
+    fn written(format: Format) -> Vec<u8> {
+        let mut out = vec![];
+        write(&image(), format, 10, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_netpbm() {
+        assert_eq!(
+            b"P4\n9 2\n\x80\x80\x60\x00",
+            written(Format::Pbm).as_slice()
+        );
+        let pgm = written(Format::Pgm);
+        assert_eq!(b"P5\n9 2\n255\n\x00\xff", &pgm[..13]);
+        assert_eq!(11 + 18, pgm.len());
+        let ppm = written(Format::Ppm);
+        assert_eq!(b"\xff\x00\x00", &ppm[ppm.len() - 24..ppm.len() - 21]);
+        assert_eq!(11 + 54, ppm.len());
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = String::from_utf8(written(Format::Svg)).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"90\""));
+        assert!(svg.contains("<rect x=\"1\" y=\"1\" width=\"2\" height=\"1\" fill=\"#ff0000\"/>"));
+        assert_eq!(3, svg.matches("<rect").count());
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Some(Format::Ppm), Format::from_path(Path::new("out.ppm")));
+        assert_eq!(Some(Format::Svg), Format::from_path(Path::new("a/b.svg")));
+        assert_eq!(None, Format::from_path(Path::new("out.png")));
+    }
+}
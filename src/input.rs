@@ -0,0 +1,118 @@
+use crate::error::{Error, Result};
+use crate::puzzle::{Parsed, Solver};
+use std::env;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Path(String),
+    Stdin,
+    Inline(String),
+}
+
+impl Source {
+    /// Source named on the command line: `-` is stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(path.to_string()),
+        }
+    }
+
+    /// The day's own input file. When `AOC_INPUT_DIR` is set the file is
+    /// looked up there instead of in `resources/`.
+    pub fn default_for(day: &dyn Solver) -> Source {
+        Source::Path(default_path(day, env::var_os("AOC_INPUT_DIR")))
+    }
+
+    /// Name used for the input in error messages.
+    pub fn name(&self) -> &str {
+        match self {
+            Source::Path(path) => path,
+            Source::Stdin => "<stdin>",
+            Source::Inline(_) => "<inline>",
+        }
+    }
+
+    /// Reads the whole input.
+    pub fn read(&self) -> Result<Vec<u8>> {
+        let mut data = vec![];
+        let result = match self {
+            Source::Path(path) => {
+                open(path).and_then(|mut reader| Ok(reader.read_to_end(&mut data)?))
+            }
+            Source::Stdin => io::stdin()
+                .lock()
+                .read_to_end(&mut data)
+                .map_err(Error::from),
+            Source::Inline(input) => return Ok(input.as_bytes().to_vec()),
+        };
+        result.map_err(|e| e.with_path(self.name()))?;
+        Ok(data)
+    }
+
+    /// Reads the input and parses it with `day`'s parser.
+    pub fn parse(&self, day: &dyn Solver) -> Result<Parsed> {
+        let result = match self {
+            Source::Path(path) => open(path).and_then(|mut reader| day.parse(&mut reader)),
+            Source::Stdin => day.parse(&mut io::stdin().lock()),
+            Source::Inline(input) => day.parse(&mut input.as_bytes()),
+        };
+        result.map_err(|e| e.with_path(self.name()))
+    }
+}
+
+fn default_path(day: &dyn Solver, dir: Option<OsString>) -> String {
+    let path = day.input_path();
+    match (dir, Path::new(path).file_name()) {
+        (Some(dir), Some(file_name)) => Path::new(&dir).join(file_name).display().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Opens a buffered reader on `path`, for passing to the `parse_*` functions.
+pub fn open(path: &str) -> Result<BufReader<File>> {
+    let file = File::open(path).map_err(|e| Error::from(e).with_path(path))?;
+    Ok(BufReader::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+
+    #[test]
+    fn test_default_path() {
+        let day12 = crate::day(12).unwrap();
+        assert_eq!("resources/day12_input.txt", default_path(day12, None));
+        assert_eq!(
+            "/tmp/inputs/day12_input.txt",
+            default_path(day12, Some("/tmp/inputs".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_inline() {
+        let day2 = crate::day(2).unwrap();
+        let input = Source::Inline("5,1,9,5\n7,5,3\n2,4,6,8\n".to_string())
+            .parse(day2)
+            .unwrap();
+        assert_eq!("18", day2.solve(&input, 1, &Context::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_error_names_source() {
+        let e = Source::Inline("1,x".to_string())
+            .parse(crate::day(2).unwrap())
+            .unwrap_err();
+        assert_eq!("<inline>:1:3: expected a number (found `x`)", e.to_string());
+        let e = Source::from_arg("resources/missing.txt")
+            .parse(crate::day(2).unwrap())
+            .unwrap_err();
+        assert!(e.to_string().starts_with("resources/missing.txt: "));
+    }
+}
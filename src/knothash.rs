@@ -19,7 +19,7 @@ pub fn hash(input: &[usize], rounds: usize) -> (Vec<usize>, String) {
             let (l, r) = numbers.split_at(*length);
             let mut left = l.to_vec();
             left.reverse();
-            left.extend_from_slice(&r);
+            left.extend_from_slice(r);
             numbers = left;
             let curpos = (skip_size + *length) % numbers_len;
             numbers.rotate_left(curpos);
@@ -0,0 +1,80 @@
+pub mod animate;
+pub mod bench;
+pub mod check;
+pub mod cli;
+pub mod config;
+pub mod context;
+pub mod cycle;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day22;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod examples;
+pub mod explain;
+pub mod fetch;
+pub mod fuzz;
+pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod knothash;
+pub mod normalize;
+pub mod parallel;
+pub mod parser;
+pub mod puzzle;
+pub mod repl;
+pub mod report;
+pub mod rng;
+pub mod runner;
+pub mod serve;
+
+pub use context::Context;
+pub use error::{Error, Result};
+pub use puzzle::{Parsed, Puzzle, Solver};
+
+static DAYS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day22::Day22,
+];
+
+/// All implemented days in calendar order.
+pub fn days() -> &'static [&'static dyn Solver] {
+    DAYS
+}
+
+pub fn day(day: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().find(|d| d.day() == day).copied()
+}
@@ -0,0 +1,165 @@
+use crate::error::Error;
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// How a day's input is cleaned up before its parser sees it, see
+/// `Puzzle::normalize`. Everything is on by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalize {
+    /// Drops a UTF-8 byte order mark at the start.
+    pub strip_bom: bool,
+    /// Turns `\r\n` line endings into `\n`.
+    pub crlf: bool,
+    /// Removes the whitespace at the end of each line, and the blank lines
+    /// and newline at the end of the input.
+    pub trim: bool,
+    /// Leaves out the blank lines.
+    pub skip_blank: bool,
+}
+
+impl Default for Normalize {
+    fn default() -> Normalize {
+        Normalize {
+            strip_bom: true,
+            crlf: true,
+            trim: true,
+            skip_blank: true,
+        }
+    }
+}
+
+/// Input after `Normalize::apply`, which remembers where its lines were.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalized {
+    pub data: Vec<u8>,
+    /// 1-based line of the original input for each line of `data`.
+    lines: Vec<usize>,
+    /// Lines in the original input.
+    total: usize,
+}
+
+impl Normalize {
+    /// Leaves the input as it is.
+    pub const NONE: Normalize = Normalize {
+        strip_bom: false,
+        crlf: false,
+        trim: false,
+        skip_blank: false,
+    };
+
+    pub fn apply(&self, data: &[u8]) -> Normalized {
+        let data = match data.strip_prefix(BOM) {
+            Some(rest) if self.strip_bom => rest,
+            _ => data,
+        };
+        let ends_in_newline = data.ends_with(b"\n");
+        let data = data.strip_suffix(b"\n").unwrap_or(data);
+        let mut kept: Vec<(usize, &[u8])> = vec![];
+        let mut total = 0;
+        if !data.is_empty() || ends_in_newline {
+            for (i, mut line) in data.split(|b| *b == b'\n').enumerate() {
+                total = i + 1;
+                if self.crlf {
+                    line = line.strip_suffix(b"\r").unwrap_or(line);
+                }
+                if self.trim {
+                    let end = line
+                        .iter()
+                        .rposition(|b| !b.is_ascii_whitespace())
+                        .map_or(0, |i| i + 1);
+                    line = &line[..end];
+                }
+                if self.skip_blank && line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                kept.push((i + 1, line));
+            }
+        }
+        if self.trim {
+            while kept.last().is_some_and(|(_, line)| line.is_empty()) {
+                kept.pop();
+            }
+        }
+        let lines: Vec<usize> = kept.iter().map(|(line_no, _)| *line_no).collect();
+        let mut data = kept
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect::<Vec<&[u8]>>()
+            .join(&b'\n');
+        if ends_in_newline && !self.trim && !lines.is_empty() {
+            data.push(b'\n');
+        }
+        Normalized { data, lines, total }
+    }
+}
+
+impl Normalized {
+    /// Moves the line of a parse error back to where it is in the original
+    /// input. Lines past the end stay past the end.
+    pub fn locate(&self, error: Error) -> Error {
+        match error {
+            Error::Parse {
+                path,
+                line,
+                column,
+                token,
+                message,
+            } => {
+                let line = match self.lines.get(line.wrapping_sub(1)) {
+                    Some(original) => *original,
+                    None => self.total + line.saturating_sub(self.lines.len()),
+                };
+                Error::Parse {
+                    path,
+                    line,
+                    column,
+                    token,
+                    message,
+                }
+            }
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(normalize: Normalize, data: &str) -> String {
+        String::from_utf8(normalize.apply(data.as_bytes()).data).unwrap()
+    }
+
+    #[test]
+    fn test_apply() {
+        let windows = "\u{feff}0: 3\r\n\r\n1: 2 \t\r\n\r\n";
+        assert_eq!("0: 3\n1: 2", apply(Normalize::default(), windows));
+        assert_eq!(windows, apply(Normalize::NONE, windows));
+        let keep_blank = Normalize {
+            skip_blank: false,
+            ..Normalize::default()
+        };
+        assert_eq!("0: 3\n\n1: 2", apply(keep_blank, windows));
+        let no_trim = Normalize {
+            trim: false,
+            ..Normalize::default()
+        };
+        assert_eq!("0: 3\n1: 2 \t\n", apply(no_trim, windows));
+        assert_eq!("", apply(Normalize::default(), "\r\n \n"));
+        assert_eq!("12", apply(Normalize::default(), "12"));
+    }
+
+    #[test]
+    fn test_locate() {
+        let input = Normalize::default().apply(b"\n0: 3\r\n\r\n1 2\r\n");
+        let e = input.locate(Error::parse(2, 1, "1 2", "expected `depth: range`"));
+        assert_eq!(
+            "<input>:4:1: expected `depth: range` (found `1 2`)",
+            e.to_string()
+        );
+        let e = input.locate(Error::parse(3, 1, "", "expected more"));
+        assert_eq!("<input>:5:1: expected more (found nothing)", e.to_string());
+        let e = input.locate(Error::solve("0 layers"));
+        assert_eq!("0 layers", e.to_string());
+    }
+}
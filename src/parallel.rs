@@ -0,0 +1,273 @@
+use crate::config::Config;
+use crate::context::{Canceller, Context, Params};
+use crate::input::Source;
+use crate::puzzle::Solver;
+use crate::runner::{self, panic_message, Answer};
+use std::collections::VecDeque;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How one part of a parallel run ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Ok,
+    Error(String),
+    Panic(String),
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Error(e) => write!(f, "error: {}", e),
+            Status::Panic(message) => write!(f, "panic: {}", message),
+            Status::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// One line of the summary table.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Parse and solve time, or the time waited before giving up.
+    pub duration: Duration,
+    pub status: Status,
+}
+
+impl From<Answer> for Row {
+    fn from(answer: Answer) -> Row {
+        let (value, status) = match answer.answer {
+            Ok(value) => (Some(value), Status::Ok),
+            Err(e) => (None, Status::Error(e.to_string())),
+        };
+        Row {
+            day: answer.day,
+            part: answer.part,
+            answer: value,
+            duration: answer.parse_time + answer.solve_time,
+            status,
+        }
+    }
+}
+
+/// Runs one day on its own thread, giving up on the parts that are not done
+/// after `timeout`. Solvers that poll their context stop then, the others
+/// keep running in the background.
+fn run_day(
+    day: &'static dyn Solver,
+    source: Source,
+    parts: &[u8],
+    timeout: Duration,
+    canceller: &Canceller,
+    params: Params,
+) -> Vec<Row> {
+    let (sender, receiver) = mpsc::channel();
+    let job_parts = parts.to_vec();
+    let ctx = Context::new()
+        .with_timeout(timeout)
+        .with_canceller(canceller.clone())
+        .with_params(params);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            runner::run_each(day, &source, &job_parts, &ctx, |answer| {
+                let _ = sender.send(Ok(answer));
+            })
+        }));
+        if let Err(payload) = result {
+            let _ = sender.send(Err(panic_message(payload)));
+        }
+    });
+
+    let start = Instant::now();
+    let mut rows: Vec<Row> = vec![];
+    while rows.len() < parts.len() {
+        let status = match receiver.recv_timeout(timeout.saturating_sub(start.elapsed())) {
+            Ok(Ok(answer)) => {
+                rows.push(answer.into());
+                continue;
+            }
+            Ok(Err(message)) => Status::Panic(message),
+            Err(_) => Status::Timeout,
+        };
+        for part in &parts[rows.len()..] {
+            rows.push(Row {
+                day: day.day(),
+                part: *part,
+                answer: None,
+                duration: start.elapsed(),
+                status: status.clone(),
+            });
+        }
+    }
+    rows
+}
+
+/// Runs every day of `jobs` on a pool of `workers` threads, each day with
+/// its own `timeout` and its parameters from `config`, and returns the
+/// rows ordered by day and part.
+pub fn run_all(
+    jobs: Vec<(&'static dyn Solver, Source)>,
+    parts: &[u8],
+    workers: usize,
+    timeout: Duration,
+    canceller: &Canceller,
+    config: &Config,
+) -> Vec<Row> {
+    let queue = Arc::new(Mutex::new(jobs.into_iter().collect::<VecDeque<_>>()));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..workers.max(1) {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        let parts = parts.to_vec();
+        let canceller = canceller.clone();
+        let config = config.clone();
+        thread::spawn(move || loop {
+            let job = queue.lock().unwrap().pop_front();
+            let (day, source) = match job {
+                Some(job) => job,
+                None => break,
+            };
+            let params = config.params(day.day());
+            for row in run_day(day, source, &parts, timeout, &canceller, params) {
+                let _ = sender.send(row);
+            }
+        });
+    }
+    drop(sender);
+
+    let mut rows: Vec<Row> = receiver.iter().collect();
+    rows.sort_by_key(|row| (row.day, row.part));
+    rows
+}
+
+/// `rows` as an aligned table with a header.
+pub fn table(rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .filter_map(|row| row.answer.as_ref().map(|a| a.len()))
+        .max()
+        .unwrap_or(0)
+        .max("answer".len());
+    let mut result = format!(
+        "day  part  {:width$}  {:>10}  status\n",
+        "answer",
+        "time",
+        width = width
+    );
+    for row in rows {
+        result += &format!(
+            "{:>3}  {:>4}  {:width$}  {:>10}  {}\n",
+            row.day,
+            row.part,
+            row.answer.as_deref().unwrap_or(""),
+            format!("{:.1?}", row.duration),
+            row.status,
+            width = width
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::rng::Rng;
+    use crate::Puzzle;
+    use std::io::BufRead;
+
+    /// Part 1 panics, part 2 never returns.
+    struct Broken;
+
+    impl Puzzle for Broken {
+        type Input = ();
+
+        fn day(&self) -> u8 {
+            30
+        }
+
+        fn input_path(&self) -> &'static str {
+            ""
+        }
+
+        fn parse(&self, _reader: &mut dyn BufRead) -> Result<()> {
+            Ok(())
+        }
+
+        fn generate(&self, _rng: &mut Rng, _size: usize) -> String {
+            String::new()
+        }
+
+        fn part1(&self, _input: &(), _ctx: &Context) -> Result<String> {
+            panic!("broken")
+        }
+
+        fn part2(&self, _input: &(), _ctx: &Context) -> Result<String> {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_all() {
+        let jobs: Vec<(&'static dyn Solver, Source)> = vec![
+            (&Broken, Source::Inline(String::new())),
+            (
+                crate::day(2).unwrap(),
+                Source::Inline("5,1,9,5".to_string()),
+            ),
+            (crate::day(1).unwrap(), Source::Inline("x".to_string())),
+        ];
+        let rows = run_all(
+            jobs,
+            &[1],
+            2,
+            Duration::from_secs(5),
+            &Canceller::new(),
+            &Config::new(),
+        );
+        let statuses: Vec<(u8, &Status)> = rows.iter().map(|r| (r.day, &r.status)).collect();
+        assert_eq!(3, statuses.len());
+        assert_eq!(
+            (1, true),
+            (statuses[0].0, matches!(statuses[0].1, Status::Error(_)))
+        );
+        assert_eq!((2, &Status::Ok), statuses[1]);
+        assert_eq!((30, &Status::Panic("broken".to_string())), statuses[2]);
+        assert_eq!(Some("8"), rows[1].answer.as_deref());
+
+        let rows = run_all(
+            vec![(&Broken, Source::Inline(String::new()))],
+            &[2],
+            1,
+            Duration::from_millis(100),
+            &Canceller::new(),
+            &Config::new(),
+        );
+        assert_eq!(Status::Timeout, rows[0].status);
+    }
+
+    #[test]
+    fn test_table() {
+        let row = Row {
+            day: 13,
+            part: 2,
+            answer: None,
+            duration: Duration::from_millis(1500),
+            status: Status::Timeout,
+        };
+        assert_eq!(
+            "day  part  answer        time  status\n 13     2                1.5s  timeout\n",
+            table(&[row])
+        );
+    }
+}
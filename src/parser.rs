@@ -0,0 +1,287 @@
+use crate::error::{Error, Result};
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A cursor over a line of input, or a part of one, that parsers take
+/// tokens from. Each token may follow spaces. Failures are parse errors at
+/// the offending token, and leave the cursor where it was.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    line_no: usize,
+    line: &'a str,
+    /// Byte range of `line` left to parse.
+    pos: usize,
+    end: usize,
+}
+
+/// Parses each line of `reader` with `f`.
+pub fn lines<R, T, F>(reader: R, mut f: F) -> Result<Vec<T>>
+where
+    R: BufRead,
+    F: FnMut(&mut Cursor) -> Result<T>,
+{
+    let mut result = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        result.push(f(&mut Cursor::new(i + 1, &line))?);
+    }
+    Ok(result)
+}
+
+fn is_word(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line_no: usize, line: &'a str) -> Cursor<'a> {
+        Cursor {
+            line_no,
+            line,
+            pos: 0,
+            end: line.len(),
+        }
+    }
+
+    /// What is left to parse.
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.pos..self.end]
+    }
+
+    /// Parse error at `token`, which must be a slice of the line.
+    pub fn error(&self, token: &str, message: &str) -> Error {
+        Error::token(self.line_no, self.line, token, message)
+    }
+
+    /// "expected ..." error at the next token, up to a space.
+    pub fn expected(&self, expected: &str) -> Error {
+        self.error(self.next_token(), &format!("expected {}", expected))
+    }
+
+    fn next_token(&self) -> &'a str {
+        let rest = self.rest().trim_start();
+        &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())]
+    }
+
+    fn skip_spaces(&mut self) {
+        self.pos = self.end - self.rest().trim_start().len();
+    }
+
+    /// Takes `len` bytes after the spaces.
+    fn take(&mut self, len: usize) -> &'a str {
+        self.skip_spaces();
+        let token = &self.rest()[..len];
+        self.pos += len;
+        token
+    }
+
+    /// Takes `literal` if it comes next.
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        let found = self.rest().trim_start().starts_with(literal);
+        if found {
+            self.take(literal.len());
+        }
+        found
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<()> {
+        match self.try_literal(literal) {
+            true => Ok(()),
+            false => Err(self.expected(&format!("`{}`", literal))),
+        }
+    }
+
+    /// Takes the longest of `literals` that comes next.
+    pub fn one_of(&mut self, literals: &[&str], expected: &str) -> Result<&'a str> {
+        let rest = self.rest().trim_start();
+        match literals
+            .iter()
+            .filter(|literal| rest.starts_with(**literal))
+            .max_by_key(|literal| literal.len())
+        {
+            Some(literal) => Ok(self.take(literal.len())),
+            None => Err(self.expected(expected)),
+        }
+    }
+
+    /// Takes letters, digits and `_`, starting with a letter or `_`.
+    pub fn identifier(&mut self, expected: &str) -> Result<&'a str> {
+        let rest = self.rest().trim_start();
+        let len = rest.find(|ch| !is_word(ch)).unwrap_or(rest.len());
+        match rest.chars().next() {
+            Some(ch) if ch.is_ascii_alphabetic() || ch == '_' => Ok(self.take(len)),
+            _ => Err(self.expected(expected)),
+        }
+    }
+
+    /// Takes a lowercase letter that is a word of its own.
+    pub fn letter(&mut self, expected: &str) -> Result<char> {
+        let rest = self.rest().trim_start();
+        let word = &rest[..rest.find(|ch| !is_word(ch)).unwrap_or(rest.len())];
+        let mut chars = word.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) if ch.is_ascii_lowercase() => {
+                self.take(1);
+                Ok(ch)
+            }
+            _ if word.is_empty() => Err(self.expected(expected)),
+            _ => Err(self.error(word, &format!("expected {}", expected))),
+        }
+    }
+
+    /// Takes digits, after an optional `-`, that are not followed by a
+    /// letter.
+    pub fn integer<T: FromStr>(&mut self, expected: &str) -> Result<T> {
+        let rest = self.rest().trim_start();
+        let sign = rest.starts_with('-') as usize;
+        let digits = rest[sign..]
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        let len = sign + digits;
+        if digits == 0 || rest[len..].starts_with(is_word) {
+            return Err(self.expected(expected));
+        }
+        match rest[..len].parse() {
+            Ok(n) => {
+                self.take(len);
+                Ok(n)
+            }
+            Err(_) => Err(self.error(&rest[..len], &format!("expected {}", expected))),
+        }
+    }
+
+    /// Splits the rest at the first `separator` into cursors over the key
+    /// and the value, as in `key: value`.
+    pub fn key_value(
+        &mut self,
+        separator: &str,
+        expected: &str,
+    ) -> Result<(Cursor<'a>, Cursor<'a>)> {
+        let rest = self.rest();
+        let i = rest
+            .find(separator)
+            .ok_or_else(|| self.error(rest.trim(), &format!("expected {}", expected)))?;
+        let key = Cursor {
+            end: self.pos + i,
+            ..*self
+        };
+        let value = Cursor {
+            pos: self.pos + i + separator.len(),
+            ..*self
+        };
+        self.pos = self.end;
+        Ok((key, value))
+    }
+
+    /// Splits the rest at each `separator` into cursors.
+    pub fn separated(self, separator: &str) -> Vec<Cursor<'a>> {
+        let mut pos = self.pos;
+        self.rest()
+            .split(separator)
+            .map(|item| {
+                let cursor = Cursor {
+                    pos,
+                    end: pos + item.len(),
+                    ..self
+                };
+                pos = cursor.end + separator.len();
+                cursor
+            })
+            .collect()
+    }
+
+    /// Parses each item of the rest, separated by `separator`, with `item`.
+    pub fn list<T, F>(self, separator: &str, mut item: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Cursor<'a>) -> Result<T>,
+    {
+        self.separated(separator)
+            .into_iter()
+            .map(|mut cursor| item(&mut cursor))
+            .collect()
+    }
+
+    /// Fails with `message` at the next token unless only spaces are left.
+    pub fn end(&self, message: &str) -> Result<()> {
+        match self.rest().trim() {
+            "" => Ok(()),
+            _ => Err(self.error(self.next_token(), message)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let mut cursor = Cursor::new(1, "b inc -5 if a_1 <= 10");
+        assert_eq!("b", cursor.identifier("a register").unwrap());
+        assert_eq!("inc", cursor.one_of(&["inc", "dec"], "`inc`").unwrap());
+        assert_eq!(-5, cursor.integer::<i64>("an amount").unwrap());
+        cursor.literal("if").unwrap();
+        assert_eq!("a_1", cursor.identifier("a register").unwrap());
+        assert_eq!("<=", cursor.one_of(&["<", "<="], "an operator").unwrap());
+        assert!(cursor.end("unexpected token").is_err());
+        assert_eq!(10, cursor.integer::<u8>("a number").unwrap());
+        cursor.end("unexpected token").unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut cursor = Cursor::new(3, "set ab 12x");
+        cursor.literal("set").unwrap();
+        assert_eq!(
+            "<input>:3:5: expected a register (found `ab`)",
+            cursor.letter("a register").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "<input>:3:5: expected `,` (found `ab`)",
+            cursor.literal(",").unwrap_err().to_string()
+        );
+        cursor.identifier("a register").unwrap();
+        assert_eq!(
+            "<input>:3:8: expected a number (found `12x`)",
+            cursor.integer::<i64>("a number").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "<input>:3:8: unexpected operand (found `12x`)",
+            cursor.end("unexpected operand").unwrap_err().to_string()
+        );
+        let mut cursor = Cursor::new(1, "add 300");
+        cursor.literal("add").unwrap();
+        assert_eq!(
+            "<input>:1:5: expected a number (found `300`)",
+            cursor.integer::<u8>("a number").unwrap_err().to_string()
+        );
+        cursor.literal("300").unwrap();
+        assert_eq!(
+            "<input>:1:8: expected a number (found nothing)",
+            cursor.integer::<u8>("a number").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let mut cursor = Cursor::new(2, "4 <-> 1, x, 300");
+        let (mut key, value) = cursor.key_value("<->", "`<->`").unwrap();
+        assert_eq!(4, key.integer::<u8>("an id").unwrap());
+        let ids = value.list(",", |item| item.integer::<u8>("an id"));
+        assert_eq!(
+            "<input>:2:10: expected an id (found `x`)",
+            ids.unwrap_err().to_string()
+        );
+        let ids = Cursor::new(2, "1, 300").list(",", |item| item.integer::<u8>("an id"));
+        assert_eq!(
+            "<input>:2:4: expected an id (found `300`)",
+            ids.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "<input>:1:1: expected `<->` (found `4 1`)",
+            Cursor::new(1, "4 1")
+                .key_value("<->", "`<->`")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use crate::animate::Simulation;
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::normalize::Normalize;
+use crate::rng::Rng;
+use std::any::Any;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Parsed input of a day, as returned by `Solver::parse`.
+pub type Parsed = Box<dyn Any + Send>;
+
+/// A day of the calendar: how to read its input and how to solve both parts.
+pub trait Puzzle: Sync {
+    type Input: Send + 'static;
+
+    fn day(&self) -> u8;
+
+    /// Path of the puzzle input, relative to the crate root.
+    fn input_path(&self) -> &'static str;
+
+    /// Reads input that went through `normalize`.
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Self::Input>;
+
+    /// How the input is cleaned up before `parse` reads it: days whose
+    /// blank lines or trailing spaces mean something turn that off.
+    fn normalize(&self) -> Normalize {
+        Normalize::default()
+    }
+
+    /// Random input in the format read by `parse`. `size` is roughly the
+    /// number of items (lines, moves, layers...) in it.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    /// Solvers with long loops poll `ctx` from them.
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Result<String>;
+
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Result<String>;
+
+    /// The simulation behind `part`, for the days that can be animated.
+    /// Setting it up may solve the part, polling `ctx`.
+    fn animation(
+        &self,
+        _input: &Self::Input,
+        _part: u8,
+        _ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        Ok(None)
+    }
+
+    /// The views `inspect` can show, each with its arguments, such as
+    /// `registers LINE`.
+    fn views(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// An intermediate structure of the solution, as text.
+    fn inspect(
+        &self,
+        _input: &Self::Input,
+        view: &str,
+        _args: &[&str],
+        _ctx: &Context,
+    ) -> Result<String> {
+        Err(unknown_view(self.views(), view))
+    }
+
+    /// The parameters the solvers read with `Context::param`, each with a
+    /// description that gives its default.
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// The problems of the normalized `input` that `parse` does not report:
+    /// those after its first error, and those it lets through but the
+    /// solvers trip over, such as `mod X 0` in day 18.
+    fn lint(&self, _input: &str, _ctx: &Context) -> Vec<Error> {
+        vec![]
+    }
+}
+
+/// Error for a `view` that is not in `views` or was given the wrong
+/// arguments.
+pub fn unknown_view(views: &[&str], view: &str) -> Error {
+    match views
+        .iter()
+        .find(|usage| usage.split_whitespace().next() == Some(view))
+    {
+        Some(usage) => Error::Solve(format!("usage: inspect {}", usage)),
+        None if views.is_empty() => Error::solve("nothing to inspect"),
+        None => Error::Solve(format!(
+            "unknown view `{}`, expected one of: {}",
+            view,
+            views.join(", ")
+        )),
+    }
+}
+
+pub fn unknown_param(day: u8, name: &str) -> Error {
+    Error::Solve(format!("day {} has no parameter `{}`", day, name))
+}
+
+/// Parses the value of the parameter or view argument `name`.
+pub fn parse_arg<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::Solve(format!("invalid {}: {}", name, value)))
+}
+
+/// Object safe view of a `Puzzle`, so that days with different input types
+/// can live in the same registry.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn input_path(&self) -> &'static str;
+
+    /// Normalizes the input, then parses it. Parse errors give the lines of
+    /// the input as it was.
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Parsed>;
+
+    fn normalize(&self) -> Normalize;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    fn solve(&self, input: &Parsed, part: u8, ctx: &Context) -> Result<String>;
+
+    fn animation(
+        &self,
+        input: &Parsed,
+        part: u8,
+        ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>>;
+
+    fn views(&self) -> &'static [&'static str];
+
+    fn inspect(&self, input: &Parsed, view: &str, args: &[&str], ctx: &Context) -> Result<String>;
+
+    fn params(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Every problem of the input, in order, without solving it.
+    fn lint(&self, reader: &mut dyn BufRead, ctx: &Context) -> Vec<Error>;
+}
+
+fn wrong_input(day: u8) -> Error {
+    Error::Solve(format!("input was not parsed by day {}", day))
+}
+
+impl<P: Puzzle> Solver for P {
+    fn day(&self) -> u8 {
+        Puzzle::day(self)
+    }
+
+    fn input_path(&self) -> &'static str {
+        Puzzle::input_path(self)
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Parsed> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        let input = Puzzle::normalize(self).apply(&data);
+        let parsed =
+            Puzzle::parse(self, &mut input.data.as_slice()).map_err(|e| input.locate(e))?;
+        Ok(Box::new(parsed))
+    }
+
+    fn normalize(&self) -> Normalize {
+        Puzzle::normalize(self)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        Puzzle::generate(self, rng, size)
+    }
+
+    fn solve(&self, input: &Parsed, part: u8, ctx: &Context) -> Result<String> {
+        let input = input
+            .downcast_ref::<P::Input>()
+            .ok_or_else(|| wrong_input(Puzzle::day(self)))?;
+        match part {
+            1 => self.part1(input, ctx),
+            2 => self.part2(input, ctx),
+            _ => Err(Error::Solve(format!(
+                "day {} has no part {}",
+                Puzzle::day(self),
+                part
+            ))),
+        }
+    }
+
+    fn animation(
+        &self,
+        input: &Parsed,
+        part: u8,
+        ctx: &Context,
+    ) -> Result<Option<Box<dyn Simulation>>> {
+        let input = input
+            .downcast_ref::<P::Input>()
+            .ok_or_else(|| wrong_input(Puzzle::day(self)))?;
+        Puzzle::animation(self, input, part, ctx)
+    }
+
+    fn views(&self) -> &'static [&'static str] {
+        Puzzle::views(self)
+    }
+
+    fn inspect(&self, input: &Parsed, view: &str, args: &[&str], ctx: &Context) -> Result<String> {
+        let input = input
+            .downcast_ref::<P::Input>()
+            .ok_or_else(|| wrong_input(Puzzle::day(self)))?;
+        Puzzle::inspect(self, input, view, args, ctx)
+    }
+
+    fn params(&self) -> &'static [(&'static str, &'static str)] {
+        Puzzle::params(self)
+    }
+
+    fn lint(&self, reader: &mut dyn BufRead, ctx: &Context) -> Vec<Error> {
+        let mut data = vec![];
+        if let Err(e) = reader.read_to_end(&mut data) {
+            return vec![e.into()];
+        }
+        let input = Puzzle::normalize(self).apply(&data);
+        let mut problems = Puzzle::lint(self, &String::from_utf8_lossy(&input.data), ctx);
+        if let Err(e) = Puzzle::parse(self, &mut input.data.as_slice()) {
+            problems.push(e);
+        }
+        let mut problems: Vec<Error> = problems.into_iter().map(|e| input.locate(e)).collect();
+        problems.sort_by_key(|e| match e {
+            Error::Parse { line, column, .. } => (*line, *column),
+            _ => (0, 0),
+        });
+        problems.dedup_by_key(|e| e.to_string());
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::Context;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_solve() {
+        let day2 = crate::day(2).unwrap();
+        let input = day2
+            .parse(&mut crate::input::open("resources/day2_testdata.csv").unwrap())
+            .unwrap();
+        assert_eq!("18", day2.solve(&input, 1, &Context::new()).unwrap());
+        assert!(day2.solve(&input, 3, &Context::new()).is_err());
+    }
+
+    #[test]
+    fn test_wrong_input() {
+        let input = crate::day(2)
+            .unwrap()
+            .parse(&mut "5,1,9,5".as_bytes())
+            .unwrap();
+        assert!(crate::day(5)
+            .unwrap()
+            .solve(&input, 1, &Context::new())
+            .is_err());
+    }
+
+    /// `input` as saved by a Windows editor, with a byte order mark, CRLF
+    /// line endings and blank lines at the end.
+    fn windows(input: &str) -> String {
+        format!("\u{feff}{}\r\n\r\n", input.replace('\n', "\r\n"))
+    }
+
+    #[test]
+    fn test_windows_inputs() {
+        let ctx = Context::new();
+        for day in crate::days() {
+            let input = day.generate(&mut Rng::new(1), 5);
+            let parsed = day.parse(&mut input.as_bytes()).unwrap();
+            let windows = match day.parse(&mut windows(&input).as_bytes()) {
+                Ok(parsed) => parsed,
+                Err(e) => panic!("day {}: {}", day.day(), e),
+            };
+            if [1, 2, 4, 5, 6, 9, 10, 11, 12, 13, 19, 22].contains(&day.day()) {
+                assert_eq!(
+                    day.solve(&parsed, 1, &ctx).unwrap(),
+                    day.solve(&windows, 1, &ctx).unwrap(),
+                    "day {}",
+                    day.day()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalized_error_line() {
+        let e = crate::day(13)
+            .unwrap()
+            .parse(&mut windows("0: 3\n\n1 2\n").as_bytes())
+            .unwrap_err();
+        assert_eq!(
+            "<input>:3:1: expected `depth: range` (found `1 2`)",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let problems: Vec<String> = crate::day(13)
+            .unwrap()
+            .lint(
+                &mut windows("0: 3\n\n1: 1\nx: 2\n").as_bytes(),
+                &Context::new(),
+            )
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "<input>:3:4: expected a range of at least 2 (found `1`)",
+                "<input>:4:1: expected a depth (found `x`)",
+            ],
+            problems
+        );
+    }
+
+    #[test]
+    fn test_generate_round_trip() {
+        for day in crate::days() {
+            for seed in 0..20 {
+                for size in [0, 1, 5, 50] {
+                    let input = day.generate(&mut Rng::new(seed), size);
+                    assert_eq!(input, day.generate(&mut Rng::new(seed), size));
+                    if let Err(e) = day.parse(&mut input.as_bytes()) {
+                        panic!(
+                            "day {} seed {} size {}: {}\n{}",
+                            day.day(),
+                            seed,
+                            size,
+                            e,
+                            input
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
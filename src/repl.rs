@@ -0,0 +1,380 @@
+use crate::config::Config;
+use crate::context::{Context, Params};
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::puzzle::{unknown_param, Parsed, Solver};
+use crate::runner::panic_message;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+/// The commands of the shell, their arguments and what they do.
+pub const COMMANDS: &[(&str, &str, &str)] = &[
+    (
+        "load",
+        "DAY [PATH]",
+        "parse PATH (default: the day's input)",
+    ),
+    (
+        "reload",
+        "",
+        "parse the loaded input again, undoing `set` and `unset`",
+    ),
+    ("part1", "", "solve part 1 of the loaded input"),
+    ("part2", "", "solve part 2 of the loaded input"),
+    (
+        "inspect",
+        "[VIEW ARGS...]",
+        "show an intermediate structure, or list the views",
+    ),
+    (
+        "params",
+        "",
+        "list the parameters of the loaded day and their values",
+    ),
+    ("set", "NAME VALUE", "change a parameter of the loaded day"),
+    ("unset", "NAME", "go back to the default of a parameter"),
+    ("days", "", "list the available days"),
+    ("help", "", "show this help"),
+    ("quit", "", "leave, as does Ctrl-D"),
+];
+
+/// What the caller does after a command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Print(String),
+    Quit,
+}
+
+struct Loaded {
+    day: &'static dyn Solver,
+    source: Source,
+    input: Parsed,
+    params: Params,
+}
+
+/// State of an interactive session: the day and input loaded last.
+#[derive(Default)]
+pub struct Session {
+    loaded: Option<Loaded>,
+    config: Config,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    /// A session that starts each day with its parameters from `config`.
+    pub fn with_config(config: Config) -> Session {
+        Session {
+            loaded: None,
+            config,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.loaded {
+            Some(loaded) => format!("day{}> ", loaded.day.day()),
+            None => "aoc> ".to_string(),
+        }
+    }
+
+    /// The day of the loaded input.
+    pub fn day(&self) -> Option<&'static dyn Solver> {
+        self.loaded.as_ref().map(|loaded| loaded.day)
+    }
+
+    /// Parses `source` with `day`, replacing the loaded input.
+    pub fn load(&mut self, day: &'static dyn Solver, source: Source) -> Result<()> {
+        let input = source.parse(day)?;
+        let params = self.config.params(day.day());
+        self.loaded = Some(Loaded {
+            day,
+            source,
+            input,
+            params,
+        });
+        Ok(())
+    }
+
+    /// Runs the command on `line`. `ctx` makes the context for each solver
+    /// run.
+    pub fn execute(&mut self, line: &str, ctx: &dyn Fn() -> Context) -> Result<Reply> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(Reply::Print(String::new())),
+        };
+        let text = match (command, args) {
+            ("quit", []) | ("exit", []) => return Ok(Reply::Quit),
+            ("help", []) => help(),
+            ("days", []) => {
+                let days: Vec<String> = crate::days().iter().map(|d| d.day().to_string()).collect();
+                days.join(" ")
+            }
+            ("load", [day]) | ("load", [day, _]) => {
+                let day = day
+                    .parse()
+                    .ok()
+                    .and_then(crate::day)
+                    .ok_or_else(|| Error::Solve(format!("day {} is not implemented", day)))?;
+                let source = match args.get(1) {
+                    Some(path) => Source::Path(path.to_string()),
+                    None => Source::default_for(day),
+                };
+                let name = source.name().to_string();
+                self.load(day, source)?;
+                format!("loaded {}", name)
+            }
+            ("reload", []) => {
+                let loaded = self.loaded()?;
+                let (day, source) = (loaded.day, loaded.source.clone());
+                self.load(day, source)?;
+                format!("loaded {}", self.loaded()?.source.name())
+            }
+            ("part1", []) => self.solve(1, ctx)?,
+            ("part2", []) => self.solve(2, ctx)?,
+            ("inspect", []) => {
+                let views = self.loaded()?.day.views();
+                if views.is_empty() {
+                    return Err(Error::solve("nothing to inspect"));
+                }
+                views.join("\n")
+            }
+            ("inspect", [view, args @ ..]) => {
+                let loaded = self.loaded()?;
+                let ctx = ctx().with_params(loaded.params.clone());
+                catch(|| loaded.day.inspect(&loaded.input, view, args, &ctx))?
+            }
+            ("params", []) => {
+                let loaded = self.loaded()?;
+                let params: Vec<String> = loaded
+                    .day
+                    .params()
+                    .iter()
+                    .map(|(name, description)| {
+                        let value = loaded.params.get(*name).map_or("-", String::as_str);
+                        format!("{:<12}{:<18}{}", name, value, description)
+                    })
+                    .collect();
+                if params.is_empty() {
+                    return Err(Error::Solve(format!(
+                        "day {} has no parameters",
+                        loaded.day.day()
+                    )));
+                }
+                params.join("\n")
+            }
+            ("set", [name, value]) => {
+                let loaded = self.loaded_param(name)?;
+                loaded.params.insert(name.to_string(), value.to_string());
+                format!("{} = {}", name, value)
+            }
+            ("unset", [name]) => {
+                self.loaded_param(name)?.params.remove(*name);
+                format!("{} is back to its default", name)
+            }
+            _ => match COMMANDS.iter().find(|(name, _, _)| *name == command) {
+                Some((name, args, _)) => {
+                    let usage = format!("usage: {} {}", name, args);
+                    return Err(Error::Solve(usage.trim_end().to_string()));
+                }
+                None => {
+                    return Err(Error::Solve(format!(
+                        "unknown command `{}`, try `help`",
+                        command
+                    )))
+                }
+            },
+        };
+        Ok(Reply::Print(text))
+    }
+
+    fn loaded(&self) -> Result<&Loaded> {
+        self.loaded.as_ref().ok_or_else(not_loaded)
+    }
+
+    /// The loaded day, if it has the parameter `name`.
+    fn loaded_param(&mut self, name: &str) -> Result<&mut Loaded> {
+        let loaded = self.loaded.as_mut().ok_or_else(not_loaded)?;
+        if !loaded.day.params().iter().any(|(param, _)| *param == name) {
+            return Err(unknown_param(loaded.day.day(), name));
+        }
+        Ok(loaded)
+    }
+
+    fn solve(&self, part: u8, ctx: &dyn Fn() -> Context) -> Result<String> {
+        let loaded = self.loaded()?;
+        let ctx = ctx().with_params(loaded.params.clone());
+        let start = Instant::now();
+        let answer = catch(|| loaded.day.solve(&loaded.input, part, &ctx))?;
+        Ok(format!("{} ({:.1?})", answer, start.elapsed()))
+    }
+
+    /// Candidates for the word before the end of `line`, with the position
+    /// that word starts at. Gives `None` where a path is expected, for the
+    /// caller to complete file names.
+    pub fn complete(&self, line: &str) -> Option<(usize, Vec<String>)> {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let (before, word) = line.split_at(start);
+        let before: Vec<&str> = before.split_whitespace().collect();
+        let candidates: Vec<String> = match before.as_slice() {
+            [] => COMMANDS
+                .iter()
+                .map(|(name, _, _)| name.to_string())
+                .collect(),
+            ["load"] => crate::days().iter().map(|d| d.day().to_string()).collect(),
+            ["load", _] => return None,
+            ["inspect"] => match &self.loaded {
+                Some(loaded) => loaded
+                    .day
+                    .views()
+                    .iter()
+                    .filter_map(|usage| usage.split_whitespace().next())
+                    .map(str::to_string)
+                    .collect(),
+                None => vec![],
+            },
+            ["set"] | ["unset"] => match &self.loaded {
+                Some(loaded) => loaded
+                    .day
+                    .params()
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .collect(),
+                None => vec![],
+            },
+            _ => vec![],
+        };
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+        Some((start, candidates))
+    }
+}
+
+fn not_loaded() -> Error {
+    Error::solve("no input loaded, try `load DAY [PATH]`")
+}
+
+/// Turns a panic of `f` into an error, so that it does not end the session.
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(Error::Solve(panic_message(payload))))
+}
+
+fn help() -> String {
+    let lines: Vec<String> = COMMANDS
+        .iter()
+        .map(|(name, args, help)| format!("{:<24}{}", format!("{} {}", name, args), help))
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(session: &mut Session, line: &str) -> Result<String> {
+        match session.execute(line, &Context::new)? {
+            Reply::Print(text) => Ok(text),
+            Reply::Quit => Ok("<quit>".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::new();
+        assert_eq!("aoc> ", session.prompt());
+        assert!(run(&mut session, "part1").is_err());
+        assert_eq!(
+            "loaded resources/day12_testdata.txt",
+            run(&mut session, "load 12 resources/day12_testdata.txt").unwrap()
+        );
+        assert_eq!("day12> ", session.prompt());
+        assert!(run(&mut session, "part1").unwrap().starts_with("6 ("));
+        assert_eq!(
+            "6 programs: 0 2 3 4 5 6\n1 programs: 1",
+            run(&mut session, "inspect groups").unwrap()
+        );
+        assert_eq!(
+            "usage: inspect group ID",
+            run(&mut session, "inspect group").unwrap_err().to_string()
+        );
+        assert!(run(&mut session, "set a 1").is_err());
+        assert!(run(&mut session, "load 3").is_err());
+        assert_eq!(
+            "usage: load DAY [PATH]",
+            run(&mut session, "load").unwrap_err().to_string()
+        );
+        assert_eq!("<quit>", run(&mut session, "quit").unwrap());
+    }
+
+    #[test]
+    fn test_params() {
+        let mut config = Config::new();
+        config.set(15, "pairs1", "5").unwrap();
+        let mut session = Session::with_config(config);
+        session
+            .load(
+                crate::day(15).unwrap(),
+                Source::Inline("1\n2\n".to_string()),
+            )
+            .unwrap();
+        run(&mut session, "set a 65").unwrap();
+        run(&mut session, "set b 8921").unwrap();
+        let params = run(&mut session, "params").unwrap();
+        let lines: Vec<&str> = params.lines().collect();
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("a           65    "));
+        assert!(lines[3].starts_with("pairs2      -     "));
+        assert_eq!(
+            "   1092455  430625591\n1181022009 1233683848\n 245556042 1431495498 *",
+            run(&mut session, "inspect values 3").unwrap()
+        );
+        assert!(run(&mut session, "part1").unwrap().starts_with("1 ("));
+        assert!(run(&mut session, "set c 1").is_err());
+        run(&mut session, "set b x").unwrap();
+        assert_eq!(
+            "invalid b: x",
+            run(&mut session, "part1").unwrap_err().to_string()
+        );
+        run(&mut session, "unset b").unwrap();
+        run(&mut session, "reload").unwrap();
+        let params = run(&mut session, "params").unwrap();
+        assert!(params.starts_with("a           -     "));
+        assert!(params.contains("\npairs1      5     "));
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut session = Session::new();
+        assert_eq!(
+            Some((
+                0,
+                vec![
+                    "part1".to_string(),
+                    "part2".to_string(),
+                    "params".to_string()
+                ]
+            )),
+            session.complete("pa")
+        );
+        assert_eq!(
+            Some((5, vec!["12".to_string()])),
+            session.complete("load 12")
+        );
+        assert_eq!(None, session.complete("load 12 res"));
+        session
+            .load(
+                crate::day(13).unwrap(),
+                Source::Inline("0: 3\n".to_string()),
+            )
+            .unwrap();
+        assert_eq!(
+            Some((8, vec!["scanners".to_string()])),
+            session.complete("inspect s")
+        );
+    }
+}
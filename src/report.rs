@@ -0,0 +1,153 @@
+use crate::runner::Answer;
+use std::fmt::Write;
+
+/// How `aoc` prints answers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    /// One JSON object per line.
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+
+    /// Line printed before the first answer, if any.
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some("day,part,answer,parse_time_us,solve_time_us,input_hash,error"),
+            _ => None,
+        }
+    }
+
+    /// `answer` as a single line.
+    pub fn line(self, answer: &Answer) -> String {
+        match self {
+            Format::Text => match &answer.answer {
+                Ok(value) => format!("day {} part {}: {}", answer.day, answer.part, value),
+                Err(e) => format!("day {} part {}: error: {}", answer.day, answer.part, e),
+            },
+            Format::Json => json(answer),
+            Format::Csv => csv(answer),
+        }
+    }
+}
+
+fn json(answer: &Answer) -> String {
+    let string_or_null = |s: Option<String>| match s {
+        Some(s) => json_string(&s),
+        None => "null".to_string(),
+    };
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_time_us\":{},\"solve_time_us\":{},\"input_hash\":{},\"error\":{}}}",
+        answer.day,
+        answer.part,
+        string_or_null(answer.answer.as_ref().ok().cloned()),
+        answer.parse_time.as_micros(),
+        answer.solve_time.as_micros(),
+        json_string(&answer.input_hash),
+        string_or_null(answer.answer.as_ref().err().map(|e| e.to_string())),
+    )
+}
+
+fn csv(answer: &Answer) -> String {
+    let fields = [
+        answer.day.to_string(),
+        answer.part.to_string(),
+        answer.answer.clone().unwrap_or_default(),
+        answer.parse_time.as_micros().to_string(),
+        answer.solve_time.as_micros().to_string(),
+        answer.input_hash.clone(),
+        answer
+            .answer
+            .as_ref()
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default(),
+    ];
+    fields
+        .iter()
+        .map(|f| csv_field(f))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// `s` as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c < ' ' => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// `s` as a CSV field, quoted only when needed.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::time::Duration;
+
+    fn answer(answer: crate::Result<String>) -> Answer {
+        Answer {
+            day: 12,
+            part: 2,
+            answer,
+            parse_time: Duration::from_micros(1500),
+            solve_time: Duration::from_micros(42),
+            input_hash: "00ff".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            r#"{"day":12,"part":2,"answer":"202","parse_time_us":1500,"solve_time_us":42,"input_hash":"00ff","error":null}"#,
+            Format::Json.line(&answer(Ok("202".to_string())))
+        );
+        let e = Error::parse(1, 2, "\"", "bad\tinput");
+        assert_eq!(
+            r#"{"day":12,"part":2,"answer":null,"parse_time_us":1500,"solve_time_us":42,"input_hash":"00ff","error":"<input>:1:2: bad\tinput (found `\\\"`)"}"#,
+            Format::Json.line(&answer(Err(e)))
+        );
+        assert_eq!(r#""\u0001""#, json_string("\u{1}"));
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "12,2,\"a,b\",1500,42,00ff,",
+            Format::Csv.line(&answer(Ok("a,b".to_string())))
+        );
+        assert_eq!(
+            "12,2,,1500,42,00ff,\"say \"\"hi\"\"\"",
+            Format::Csv.line(&answer(Err(Error::solve("say \"hi\""))))
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::puzzle::Solver;
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+/// Outcome of solving one part of one day.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// `input_hash` of the input the answer was computed from, empty if the
+    /// input could not be read.
+    pub input_hash: String,
+}
+
+/// 64-bit FNV-1a hash of `data` as 16 hex digits.
+pub fn input_hash(data: &[u8]) -> String {
+    let hash = data.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Reads `source`, parses it once and solves each of `parts`, timing the
+/// parse and every part separately. A read or parse error is reported as
+/// the answer of every part.
+pub fn run(day: &dyn Solver, source: &Source, parts: &[u8], ctx: &Context) -> Vec<Answer> {
+    let mut answers = vec![];
+    run_each(day, source, parts, ctx, |answer| answers.push(answer));
+    answers
+}
+
+/// Same as `run`, but hands over each answer as soon as it is known.
+pub fn run_each<F>(day: &dyn Solver, source: &Source, parts: &[u8], ctx: &Context, mut f: F)
+where
+    F: FnMut(Answer),
+{
+    let answer = |part, answer, parse_time, solve_time, input_hash: &str| Answer {
+        day: day.day(),
+        part,
+        answer,
+        parse_time,
+        solve_time,
+        input_hash: input_hash.to_string(),
+    };
+    let mut failed = |e: Error, parse_time, input_hash: &str| {
+        for part in parts {
+            f(answer(
+                *part,
+                Err(e.clone()),
+                parse_time,
+                Duration::default(),
+                input_hash,
+            ));
+        }
+    };
+
+    let data = match source.read() {
+        Ok(data) => data,
+        Err(e) => return failed(e, Duration::default(), ""),
+    };
+    let hash = input_hash(&data);
+
+    let start = Instant::now();
+    let parsed = day.parse(&mut data.as_slice());
+    let parse_time = start.elapsed();
+    let input = match parsed {
+        Ok(input) => input,
+        Err(e) => return failed(e.with_path(source.name()), parse_time, &hash),
+    };
+
+    for part in parts {
+        let start = Instant::now();
+        let result = day.solve(&input, *part, ctx);
+        f(answer(*part, result, parse_time, start.elapsed(), &hash));
+    }
+}
+
+/// Message of a caught panic.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!("cbf29ce484222325", input_hash(b""));
+        assert_eq!("af63dc4c8601ec8c", input_hash(b"a"));
+        assert_eq!("85944171f73967e8", input_hash(b"foobar"));
+    }
+
+    #[test]
+    fn test_run() {
+        let source = Source::Inline("5 1 9 5".replace(' ', ","));
+        let answers = run(crate::day(2).unwrap(), &source, &[1, 2], &Context::new());
+        assert_eq!(2, answers.len());
+        assert_eq!("8", answers[0].answer.as_ref().unwrap());
+        assert_eq!(input_hash(b"5,1,9,5"), answers[1].input_hash);
+    }
+
+    #[test]
+    fn test_run_parse_error() {
+        let source = Source::Inline("5,x".to_string());
+        let answers = run(crate::day(2).unwrap(), &source, &[1, 2], &Context::new());
+        assert_eq!(2, answers.len());
+        for answer in answers {
+            let e = answer.answer.unwrap_err();
+            assert_eq!("<inline>:1:3: expected a number (found `x`)", e.to_string());
+        }
+    }
+}
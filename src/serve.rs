@@ -0,0 +1,425 @@
+use crate::context::{Canceller, Context};
+use crate::error::Error;
+use crate::input::Source;
+use crate::report::json_string;
+use crate::runner::{self, panic_message, Answer};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Largest accepted request body, in bytes.
+pub const MAX_BODY: usize = 1 << 20;
+
+const MAX_LINE: u64 = 8192;
+const MAX_HEADERS: usize = 100;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// A JSON response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Response {
+        Response { status: 200, body }
+    }
+
+    /// Error that is not about the puzzle input.
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!(
+                "{{\"error\":{{\"kind\":\"request\",\"message\":{}}}}}",
+                json_string(message)
+            ),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            _ => "",
+        }
+    }
+
+    pub fn write_to(&self, out: &mut dyn Write) -> io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )?;
+        out.flush()
+    }
+}
+
+/// Reads one line of at most `MAX_LINE` bytes, without its line ending.
+fn read_line(reader: &mut dyn BufRead) -> Result<String, Response> {
+    let mut line = String::new();
+    let read = reader.take(MAX_LINE).read_line(&mut line);
+    match read {
+        Ok(0) => return Err(Response::error(400, "incomplete request")),
+        Ok(_) if !line.ends_with('\n') => return Err(Response::error(400, "line too long")),
+        Ok(_) => {}
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            return Err(Response::error(408, "timed out reading the request"))
+        }
+        Err(_) => return Err(Response::error(400, "unreadable request")),
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Reads an HTTP/1.x request, refusing bodies larger than `max_body`.
+/// Failures come back as the response to send.
+pub fn read_request(reader: &mut dyn BufRead, max_body: usize) -> Result<Request, Response> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (method, path) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/1.") => {
+            (method.to_string(), path.to_string())
+        }
+        _ => return Err(Response::error(400, "malformed request line")),
+    };
+
+    let mut length = None;
+    for i in 0.. {
+        if i == MAX_HEADERS {
+            return Err(Response::error(400, "too many headers"));
+        }
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| Response::error(400, "malformed header"))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            let value = value.trim().parse::<usize>();
+            length = Some(value.map_err(|_| Response::error(400, "invalid Content-Length"))?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(411, "chunked bodies are not supported"));
+        }
+    }
+
+    let length = match (length, method.as_str()) {
+        (Some(length), _) => length,
+        (None, "POST") => return Err(Response::error(411, "Content-Length is required")),
+        (None, _) => 0,
+    };
+    if length > max_body {
+        let message = format!("body larger than {} bytes", max_body);
+        return Err(Response::error(413, &message));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "incomplete body"))?;
+    Ok(Request { method, path, body })
+}
+
+fn error_json(e: &Error) -> String {
+    match e {
+        Error::Parse {
+            line,
+            column,
+            token,
+            message,
+            ..
+        } => format!(
+            "{{\"kind\":\"parse\",\"line\":{},\"column\":{},\"token\":{},\"message\":{}}}",
+            line,
+            column,
+            json_string(token),
+            json_string(message)
+        ),
+        Error::Cancelled => "{\"kind\":\"cancelled\",\"message\":\"cancelled\"}".to_string(),
+        e => format!(
+            "{{\"kind\":\"solve\",\"message\":{}}}",
+            json_string(&e.to_string())
+        ),
+    }
+}
+
+fn answer_response(answer: &Answer) -> Response {
+    let (status, result) = match &answer.answer {
+        Ok(value) => (200, format!("\"answer\":{}", json_string(value))),
+        Err(Error::Cancelled) => (503, format!("\"error\":{}", error_json(&Error::Cancelled))),
+        Err(e) => (422, format!("\"error\":{}", error_json(e))),
+    };
+    Response {
+        status,
+        body: format!(
+            "{{\"day\":{},\"part\":{},{},\"parse_time_us\":{},\"solve_time_us\":{},\"input_hash\":{}}}",
+            answer.day,
+            answer.part,
+            result,
+            answer.parse_time.as_micros(),
+            answer.solve_time.as_micros(),
+            json_string(&answer.input_hash)
+        ),
+    }
+}
+
+/// Answers `GET /days` and `POST /days/{n}/parts/{p}`, giving each solver
+/// the context made by `ctx`.
+pub fn handle(request: &Request, ctx: &dyn Fn() -> Context) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let method = request.method.as_str();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days: Vec<String> = crate::days().iter().map(|d| d.day().to_string()).collect();
+            Response::ok(format!("{{\"days\":[{}]}}", days.join(",")))
+        }
+        ("POST", ["days", day, "parts", part]) => {
+            let day = match day.parse().ok().and_then(crate::day) {
+                Some(day) => day,
+                None => return Response::error(404, "no such day"),
+            };
+            let part = match part.parse::<u8>() {
+                Ok(part @ 1..=2) => part,
+                _ => return Response::error(404, "no such part"),
+            };
+            let input = match String::from_utf8(request.body.clone()) {
+                Ok(input) => input,
+                Err(_) => return Response::error(400, "body is not UTF-8"),
+            };
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                runner::run(day, &Source::Inline(input), &[part], &ctx())
+            }));
+            match result {
+                Ok(answers) => answer_response(&answers[0]),
+                Err(payload) => Response::error(500, &panic_message(payload)),
+            }
+        }
+        (_, ["days"]) | (_, ["days", _, "parts", _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn handle_connection(stream: TcpStream, timeout: Option<Duration>, canceller: Canceller) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader, MAX_BODY) {
+        Ok(request) => handle(&request, &|| {
+            let ctx = Context::new().with_canceller(canceller.clone());
+            match timeout {
+                Some(timeout) => ctx.with_timeout(timeout),
+                None => ctx,
+            }
+        }),
+        Err(response) => response,
+    };
+    let _ = response.write_to(&mut &stream);
+}
+
+/// Answers 503 without reading the request, when every worker is busy.
+fn reject(stream: TcpStream) {
+    let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+    let _ = Response::error(503, "server is busy").write_to(&mut &stream);
+}
+
+/// Serves requests on `listener` with a pool of `workers` threads, until
+/// `canceller` is cancelled. Each solver gets `timeout`. Up to `workers`
+/// connections wait for a free worker, the others get 503.
+pub fn serve(
+    listener: TcpListener,
+    workers: usize,
+    timeout: Option<Duration>,
+    canceller: &Canceller,
+) -> io::Result<()> {
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(workers.max(1));
+    let receiver = Arc::new(Mutex::new(receiver));
+    let pool: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+            let receiver = Arc::clone(&receiver);
+            let canceller = canceller.clone();
+            thread::spawn(move || loop {
+                let stream = receiver.lock().unwrap().recv();
+                match stream {
+                    Ok(stream) => handle_connection(stream, timeout, canceller.clone()),
+                    Err(_) => break,
+                }
+            })
+        })
+        .collect();
+
+    // Poll, so that cancelling stops the server.
+    listener.set_nonblocking(true)?;
+    let result = loop {
+        if canceller.is_cancelled() {
+            break Ok(());
+        }
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = stream.set_nonblocking(false) {
+                    break Err(e);
+                }
+                if let Err(TrySendError::Full(stream)) = sender.try_send(stream) {
+                    reject(stream);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(20));
+            }
+            Err(e) => break Err(e),
+        }
+    };
+    drop(sender);
+    for worker in pool {
+        let _ = worker.join();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    fn request(method: &str, path: &str, body: &str) -> Response {
+        let request = Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.as_bytes().to_vec(),
+        };
+        handle(&request, &Context::new)
+    }
+
+    #[test]
+    fn test_handle() {
+        let response = request("GET", "/days", "");
+        assert_eq!(200, response.status);
+        assert!(response.body.starts_with("{\"days\":[1,2,4,"));
+
+        let response = request("POST", "/days/2/parts/1", "5,1,9,5\n7,5,3\n2,4,6,8\n");
+        assert_eq!(200, response.status);
+        assert!(response
+            .body
+            .starts_with("{\"day\":2,\"part\":1,\"answer\":\"18\","));
+
+        let response = request("POST", "/days/13/parts/1", "0: 3\n1 2\n");
+        assert_eq!(422, response.status);
+        assert!(response.body.contains(
+            "\"error\":{\"kind\":\"parse\",\"line\":2,\"column\":1,\"token\":\"1 2\",\
+             \"message\":\"expected `depth: range`\"}"
+        ));
+
+        assert_eq!(404, request("POST", "/days/3/parts/1", "").status);
+        assert_eq!(404, request("POST", "/days/2/parts/3", "").status);
+        assert_eq!(405, request("GET", "/days/2/parts/1", "").status);
+        assert_eq!(404, request("GET", "/", "").status);
+    }
+
+    #[test]
+    fn test_read_request() {
+        let mut input = &b"POST /days/1/parts/1 HTTP/1.1\r\nContent-Length: 4\r\n\r\n1122"[..];
+        let request = read_request(&mut input, MAX_BODY).unwrap();
+        assert_eq!(
+            ("POST", "/days/1/parts/1"),
+            (&*request.method, &*request.path)
+        );
+        assert_eq!(b"1122", request.body.as_slice());
+
+        let mut input = &b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n12345"[..];
+        assert_eq!(413, read_request(&mut input, 4).unwrap_err().status);
+        let mut input = &b"POST / HTTP/1.1\r\n\r\n"[..];
+        assert_eq!(411, read_request(&mut input, 4).unwrap_err().status);
+        let mut input = &b"nonsense\r\n\r\n"[..];
+        assert_eq!(400, read_request(&mut input, 4).unwrap_err().status);
+    }
+
+    fn exchange(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let canceller = Canceller::new();
+        let server = {
+            let canceller = canceller.clone();
+            thread::spawn(move || serve(listener, 2, None, &canceller))
+        };
+
+        let response = exchange(
+            addr,
+            "POST /days/1/parts/1 HTTP/1.1\r\nHost: x\r\nContent-Length: 4\r\n\r\n1122",
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"3\""));
+
+        let response = exchange(
+            addr,
+            &format!(
+                "POST /days/1/parts/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+                MAX_BODY + 1
+            ),
+        );
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+
+        canceller.cancel();
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_busy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let canceller = Canceller::new();
+        let server = {
+            let canceller = canceller.clone();
+            thread::spawn(move || serve(listener, 1, None, &canceller))
+        };
+
+        // The worker waits for the first request, the second one waits for
+        // the worker.
+        let first = TcpStream::connect(addr).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let second = TcpStream::connect(addr).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let response = exchange(addr, "");
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(response.contains("server is busy"));
+
+        drop((first, second));
+        canceller.cancel();
+        server.join().unwrap().unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use aoc_2017::fetch::{self, Fetched};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+/// Serves `bodies` in order, one connection each, and reports every
+/// request line with its Cookie header.
+fn stub_server(bodies: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/2017", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for (status, body) in bodies {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut cookie = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Cookie: ") {
+                    cookie = value.trim().to_string();
+                }
+            }
+            sender
+                .send((request_line.trim().to_string(), cookie))
+                .unwrap();
+            write!(
+                &stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (url, receiver)
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_fetch_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_fetch_and_cache() {
+    let (url, requests) = stub_server(vec![(200, "0 <-> 0\n")]);
+    let dir = scratch("cache");
+    let path = dir.join("day12_input.txt");
+
+    let fetched = fetch::fetch(12, &url, Some("secret"), &path).unwrap();
+    assert_eq!(Fetched::Downloaded(8), fetched);
+    assert_eq!("0 <-> 0\n", fs::read_to_string(&path).unwrap());
+    let (request_line, cookie) = requests.recv().unwrap();
+    assert_eq!("GET /2017/day/12/input HTTP/1.1", request_line);
+    assert_eq!("session=secret", cookie);
+
+    // Cached: neither the server nor the token are needed any more.
+    assert_eq!(
+        Fetched::Cached,
+        fetch::fetch(12, &url, None, &path).unwrap()
+    );
+    assert!(requests.try_recv().is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_fetch_errors() {
+    let (url, _requests) = stub_server(vec![(400, "Please log in"), (200, "")]);
+    let dir = scratch("errors");
+    let path = dir.join("day1_input.txt");
+
+    let e = fetch::fetch(1, &url, Some("expired"), &path).unwrap_err();
+    assert_eq!(format!("{}/day/1/input: HTTP 400 X", url), e.to_string());
+    let e = fetch::fetch(1, &url, Some("expired"), &path).unwrap_err();
+    assert!(e.to_string().ends_with("empty input"));
+    assert!(!path.exists());
+
+    assert!(fetch::fetch(1, &url, None, &path)
+        .unwrap_err()
+        .to_string()
+        .starts_with("no session token"));
+    let _ = fs::remove_dir_all(&dir);
+}